@@ -25,3 +25,11 @@ pub(crate) use inject_struct::StructInjector;
 pub(crate) use inject_trait::TraitInjector;
 pub(crate) use injection::{InjectedCallSite, InjectionStrategy};
 pub(crate) use injector::CallSiteInjector;
+
+use proc_macro2::{Ident, Span};
+
+// the service provider is named with mixed-site hygiene so it cannot collide
+// with the name of a runtime argument declared by the constructor
+pub(crate) fn provider() -> Ident {
+    Ident::new("sp", Span::mixed_site())
+}
@@ -115,6 +115,8 @@ impl CallSite {
                     format!("Expected 1-2 type arguments, but found {count}."),
                 )),
             }
+        } else if context.assisted {
+            Ok(Box::new(StructInjector::new(context.type_)))
         } else if context.scoped
             || context.type_.path.segments.last().unwrap().ident == Ident::new("ServiceProvider", Span::call_site())
         {
@@ -150,6 +152,25 @@ impl CallSite {
                     }
                     _ => outer,
                 }
+            } else if let Some(inner) = Self::try_visit_factory(outer) {
+                match inner {
+                    Type::Path(path) => {
+                        builder.is_factory();
+                        path
+                    }
+                    _ => outer,
+                }
+            } else if let Some(inner) = Self::try_visit_owned(outer) {
+                match inner {
+                    Type::Path(path) => {
+                        builder.is_owned();
+                        path
+                    }
+                    _ => outer,
+                }
+            } else if Self::is_assisted_factory(outer) {
+                builder.is_assisted();
+                outer
             } else {
                 outer
             };
@@ -231,6 +252,28 @@ impl CallSite {
         Self::visit_generic_type_arg(type_, "Lazy")
     }
 
+    #[inline]
+    fn try_visit_factory(type_: &TypePath) -> Option<&Type> {
+        let args = Self::visit_generic_type_args(type_, "Factory");
+
+        if args.len() == 1 {
+            Some(args[0])
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn try_visit_owned(type_: &TypePath) -> Option<&Type> {
+        Self::visit_generic_type_arg(type_, "Owned")
+    }
+
+    // Factory<T, A> is registered as a service in its own right
+    #[inline]
+    fn is_assisted_factory(type_: &TypePath) -> bool {
+        Self::visit_generic_type_args(type_, "Factory").len() == 2
+    }
+
     #[inline]
     fn try_visit_option(type_: &TypePath) -> Option<&Type> {
         Self::visit_generic_type_arg(type_, "Option")
@@ -284,6 +327,9 @@ impl CallSite {
      * KeyedRef<K,T>
      * KeyedRefMut<K,T>
      * Lazy<T>
+     * Factory<T>
+     * Factory<T,A>
+     * Owned<T>
      * Option<T>
      * Vec<T>
      */
@@ -1,7 +1,11 @@
 use super::{CallSite, InjectedCallSite};
 use proc_macro2::{Ident, Span};
+use quote::ToTokens;
 use syn::punctuated::Punctuated;
-use syn::{spanned::Spanned, Attribute, Error, FnArg, ImplItem, ItemImpl, Meta, Path, Result, Signature, Token};
+use syn::{
+    spanned::Spanned, Attribute, Error, FnArg, ImplItem, ItemImpl, Meta, Pat, PatType, Path, Result, ReturnType,
+    Signature, Token, Type,
+};
 
 fn is_inject(attribute: &Attribute) -> bool {
     if attribute.path().segments.last().unwrap().ident == "inject" {
@@ -23,6 +27,11 @@ fn is_inject(attribute: &Attribute) -> bool {
     false
 }
 
+// #[arg] marks a parameter that is supplied at runtime rather than injected
+fn is_arg(attribute: &Attribute) -> bool {
+    attribute.path().is_ident("arg")
+}
+
 pub struct Constructor;
 
 impl Constructor {
@@ -70,6 +79,54 @@ impl Constructor {
         }
     }
 
+    pub fn is_fallible(ctor: &Signature) -> bool {
+        if let ReturnType::Type(_, ref type_) = ctor.output {
+            if let Type::Path(ref path) = **type_ {
+                return path
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Result");
+            }
+        }
+
+        false
+    }
+
+    pub fn arguments(ctor: &Signature) -> Vec<(Ident, &Type)> {
+        ctor.inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(type_) if type_.attrs.iter().any(is_arg) => Some(type_),
+                _ => None,
+            })
+            .enumerate()
+            .map(|(index, type_)| (Self::argument_name(index, type_), &*type_.ty))
+            .collect()
+    }
+
+    // the parameter name is reused when possible so the generated code reads naturally
+    fn argument_name(index: usize, type_: &PatType) -> Ident {
+        if let Pat::Ident(ref pat) = *type_.pat {
+            pat.ident.clone()
+        } else {
+            Ident::new(&format!("arg{}", index + 1), Span::call_site())
+        }
+    }
+
+    // #[arg] is not a real attribute so it must be removed from the emitted implementation
+    pub fn strip_arguments(impl_: &mut ItemImpl) {
+        for item in &mut impl_.items {
+            if let ImplItem::Fn(method) = item {
+                for input in &mut method.sig.inputs {
+                    if let FnArg::Typed(type_) = input {
+                        type_.attrs.retain(|attribute| !is_arg(attribute));
+                    }
+                }
+            }
+        }
+    }
+
     pub fn visit(ctor: &Signature) -> Result<Vec<InjectedCallSite>> {
         let count = ctor.inputs.len();
 
@@ -78,9 +135,17 @@ impl Constructor {
         }
 
         let mut callsites = Vec::with_capacity(count);
+        let mut arguments = 0;
 
         for input in ctor.inputs.iter() {
             let callsite = match input {
+                FnArg::Typed(ref type_) if type_.attrs.iter().any(is_arg) => {
+                    arguments += 1;
+                    InjectedCallSite {
+                        resolve: Self::argument_name(arguments - 1, type_).to_token_stream(),
+                        dependency: None,
+                    }
+                }
                 FnArg::Typed(ref type_) => CallSite::visit(&type_.ty, false)?,
                 _ => {
                     return Err(Error::new(
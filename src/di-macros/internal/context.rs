@@ -1,3 +1,6 @@
+use super::provider;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
 use syn::TypePath;
 
 pub struct CallSiteContext<'a> {
@@ -5,6 +8,9 @@ pub struct CallSiteContext<'a> {
     pub optional: bool,
     pub many: bool,
     pub lazy: bool,
+    pub factory: bool,
+    pub owned: bool,
+    pub assisted: bool,
     pub iterator: bool,
     pub mutable: bool,
     pub scoped: bool,
@@ -14,6 +20,47 @@ impl<'a> CallSiteContext<'a> {
     pub fn optional_of_many(&self) -> bool {
         self.optional && self.many
     }
+
+    // the module that wraps resolution of the call site, if any
+    pub fn wrapper(&self) -> Option<Wrapper> {
+        if self.lazy {
+            Some(Wrapper::new("lazy", false))
+        } else if self.factory {
+            Some(Wrapper::new("factory", false))
+        } else if self.owned {
+            // an owned service is resolved when it is injected so a failure is propagated
+            Some(Wrapper::new("owned", true))
+        } else {
+            None
+        }
+    }
+}
+
+pub struct Wrapper {
+    module: Ident,
+    fallible: bool,
+}
+
+impl Wrapper {
+    fn new(module: &str, fallible: bool) -> Self {
+        Self {
+            module: Ident::new(module, Span::call_site()),
+            fallible,
+        }
+    }
+
+    pub fn resolve(&self, function: &str, generics: TokenStream) -> TokenStream {
+        let module = &self.module;
+        let sp = provider();
+
+        if self.fallible {
+            let function = format_ident!("try_{}", function);
+            quote! { di::#module::#function::<#generics>(#sp.clone())? }
+        } else {
+            let function = Ident::new(function, Span::call_site());
+            quote! { di::#module::#function::<#generics>(#sp.clone()) }
+        }
+    }
 }
 
 #[derive(Default)]
@@ -22,6 +69,9 @@ pub struct CallSiteContextBuilder<'a> {
     optional: bool,
     many: bool,
     lazy: bool,
+    factory: bool,
+    owned: bool,
+    assisted: bool,
     iterator: bool,
     mutable: bool,
     scoped: bool,
@@ -44,6 +94,18 @@ impl<'a> CallSiteContextBuilder<'a> {
         self.lazy = true
     }
 
+    pub fn is_factory(&mut self) {
+        self.factory = true
+    }
+
+    pub fn is_owned(&mut self) {
+        self.owned = true
+    }
+
+    pub fn is_assisted(&mut self) {
+        self.assisted = true
+    }
+
     pub fn is_iterator(&mut self) {
         self.iterator = true;
         self.many = true
@@ -63,6 +125,9 @@ impl<'a> CallSiteContextBuilder<'a> {
             optional: self.optional,
             many: self.many,
             lazy: self.lazy,
+            factory: self.factory,
+            owned: self.owned,
+            assisted: self.assisted,
             iterator: self.iterator,
             mutable: self.mutable,
             scoped: self.scoped,
@@ -1,4 +1,4 @@
-use super::{Constructor, DeriveContext, Fields, MacroTarget};
+use super::{provider, Constructor, DeriveContext, Fields, MacroTarget};
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::Result;
@@ -29,13 +29,19 @@ impl InjectableTrait {
             quote! { Self }
         };
 
+        let sp = provider();
         let implementation = &context.implementation;
         let depends_on = quote! { #(.depends_on(#deps))* };
         let (generics, _, where_) = context.generics.split_for_impl();
         let activate = match *context.target() {
             MacroTarget::Method(method) => {
                 let fn_ = &method.ident;
-                quote! { Self::#fn_(#(#args),*) }
+
+                if Constructor::is_fallible(method) {
+                    quote! { Self::#fn_(#(#args),*).map_err(di::Activator::error)? }
+                } else {
+                    quote! { Self::#fn_(#(#args),*) }
+                }
             }
             MacroTarget::Struct(struct_) => match &struct_.fields {
                 syn::Fields::Named(fields) => {
@@ -46,17 +52,51 @@ impl InjectableTrait {
                 syn::Fields::Unit => quote! { Self },
             },
         };
-        let activate2 = activate.clone();
-        let code = quote! {
-            impl #generics di::Injectable for #implementation #where_ {
-                fn inject(lifetime: di::ServiceLifetime) -> di::InjectBuilder {
-                    di::InjectBuilder::new(
-                        di::Activator::new::<#service, Self>(
-                            |sp: &di::ServiceProvider| di::Ref::new(#activate),
-                            |sp: &di::ServiceProvider| di::RefMut::new(#activate2.into())
-                        ),
-                        lifetime
-                    )#depends_on
+        let arguments = match *context.target() {
+            MacroTarget::Method(method) => Constructor::arguments(method),
+            MacroTarget::Struct(_) => Vec::new(),
+        };
+        let code = if arguments.is_empty() {
+            let activate2 = activate.clone();
+
+            quote! {
+                impl #generics di::Injectable for #implementation #where_ {
+                    fn inject(lifetime: di::ServiceLifetime) -> di::InjectBuilder {
+                        di::InjectBuilder::new(
+                            di::Activator::try_new::<#service, Self>(
+                                |#sp: &di::ServiceProvider| Ok(di::Ref::new(#activate)),
+                                |#sp: &di::ServiceProvider| Ok(di::RefMut::new(#activate2.into()))
+                            ),
+                            lifetime
+                        )#depends_on
+                    }
+                }
+            }
+        } else {
+            // runtime arguments are supplied through an assisted factory, which is the registered service
+            let names: Vec<_> = arguments.iter().map(|(name, _)| name).collect();
+            let types: Vec<_> = arguments.iter().map(|(_, type_)| type_).collect();
+            let factory = quote! { di::factory::Factory<di::Ref<#service>, (#(#types,)*)> };
+            let create = quote! {
+                di::factory::assisted(
+                    #sp.clone(),
+                    |#sp: &di::ServiceProvider, (#(#names,)*): (#(#types,)*)|
+                        -> Result<di::Ref<#service>, di::ResolveError> {
+                        Ok(di::Ref::new(#activate))
+                    })
+            };
+
+            quote! {
+                impl #generics di::Injectable for #implementation #where_ {
+                    fn inject(lifetime: di::ServiceLifetime) -> di::InjectBuilder {
+                        di::InjectBuilder::new(
+                            di::Activator::new::<#factory, Self>(
+                                |#sp: &di::ServiceProvider| di::Ref::new(#create),
+                                |#sp: &di::ServiceProvider| di::RefMut::new(#create.into())
+                            ),
+                            lifetime
+                        )#depends_on
+                    }
                 }
             }
         };
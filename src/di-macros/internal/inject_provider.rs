@@ -1,17 +1,28 @@
-use super::{CallSiteContext, InjectedCallSite, InjectionStrategy};
+use super::{provider, CallSiteContext, InjectedCallSite, InjectionStrategy};
 use quote::quote;
 
 pub struct ServiceProviderInjector;
 
 impl InjectionStrategy for ServiceProviderInjector {
     fn inject(&self, context: &CallSiteContext) -> InjectedCallSite {
+        let sp = provider();
+
         InjectedCallSite {
             resolve: if context.scoped {
-                quote! { ScopedServiceProvider::from(sp) }
+                quote! { ScopedServiceProvider::from(#sp) }
+            } else {
+                quote! { #sp.clone() }
+            },
+            // a scoped provider is a new scope, but the provider itself is whichever
+            // provider activated the service, which may be captured by a singleton
+            dependency: if context.scoped {
+                None
             } else {
-                quote! { sp.clone() }
+                Some(quote! {
+                di::ServiceDependency::new(
+                    di::Type::of::<di::ServiceProvider>(),
+                    di::ServiceCardinality::ZeroOrOne) })
             },
-            dependency: None,
         }
     }
 }
@@ -1,4 +1,4 @@
-use super::{CallSiteContext, InjectedCallSite, InjectionStrategy};
+use super::{provider, CallSiteContext, InjectedCallSite, InjectionStrategy};
 use quote::{quote, ToTokens};
 use syn::TypePath;
 
@@ -11,19 +11,20 @@ pub trait CallSiteInjector<'a> {
 
     fn optional(&self, context: &CallSiteContext) -> InjectedCallSite {
         let svc = self.service();
+        let sp = provider();
 
         if let Some(key) = self.key() {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::zero_or_one_with_key_mut::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_one_with_key_mut", quote! { #key, #svc })
                     } else {
-                        quote! { di::lazy::zero_or_one_with_key::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_one_with_key", quote! { #key, #svc })
                     }
                 } else if context.mutable {
-                    quote! { sp.get_by_key_mut::<#key, #svc>() }
+                    quote! { #sp.get_by_key_mut::<#key, #svc>() }
                 } else {
-                    quote! { sp.get_by_key::<#key, #svc>() }
+                    quote! { #sp.get_by_key::<#key, #svc>() }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -39,16 +40,16 @@ pub trait CallSiteInjector<'a> {
             }
         } else {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::zero_or_one_mut::<#svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_one_mut", quote! { #svc })
                     } else {
-                        quote! { di::lazy::zero_or_one::<#svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_one", quote! { #svc })
                     }
                 } else if context.mutable {
-                    quote! { sp.get_mut::<#svc>() }
+                    quote! { #sp.get_mut::<#svc>() }
                 } else {
-                    quote! { sp.get::<#svc>() }
+                    quote! { #sp.get::<#svc>() }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -67,19 +68,20 @@ pub trait CallSiteInjector<'a> {
 
     fn required(&self, context: &CallSiteContext) -> InjectedCallSite {
         let svc = self.service();
+        let sp = provider();
 
         if let Some(key) = self.key() {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::exactly_one_with_key_mut::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("exactly_one_with_key_mut", quote! { #key, #svc })
                     } else {
-                        quote! { di::lazy::exactly_one_with_key::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("exactly_one_with_key", quote! { #key, #svc })
                     }
                 } else if context.mutable {
-                    quote! { sp.get_required_by_key_mut::<#key, #svc>() }
+                    quote! { #sp.try_get_required_by_key_mut::<#key, #svc>()? }
                 } else {
-                    quote! { sp.get_required_by_key::<#key, #svc>() }
+                    quote! { #sp.try_get_required_by_key::<#key, #svc>()? }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -95,16 +97,18 @@ pub trait CallSiteInjector<'a> {
             }
         } else {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::exactly_one_mut::<#svc>(sp.clone()) }
+                        wrapper.resolve("exactly_one_mut", quote! { #svc })
                     } else {
-                        quote! { di::lazy::exactly_one::<#svc>(sp.clone()) }
+                        wrapper.resolve("exactly_one", quote! { #svc })
                     }
+                } else if context.assisted {
+                    quote! { (*#sp.try_get_required::<#svc>()?).clone() }
                 } else if context.mutable {
-                    quote! { sp.get_required_mut::<#svc>() }
+                    quote! { #sp.try_get_required_mut::<#svc>()? }
                 } else {
-                    quote! { sp.get_required::<#svc>() }
+                    quote! { #sp.try_get_required::<#svc>()? }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -123,25 +127,26 @@ pub trait CallSiteInjector<'a> {
 
     fn many(&self, context: &CallSiteContext) -> InjectedCallSite {
         let svc = self.service();
+        let sp = provider();
 
         if let Some(key) = self.key() {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::zero_or_more_with_key_mut::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_more_with_key_mut", quote! { #key, #svc })
                     } else {
-                        quote! { di::lazy::zero_or_more_with_key::<#key, #svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_more_with_key", quote! { #key, #svc })
                     }
                 } else if context.iterator {
                     if context.mutable {
-                        quote! { sp.get_all_by_key_mut::<#key, #svc>() }
+                        quote! { #sp.get_all_by_key_mut::<#key, #svc>() }
                     } else {
-                        quote! { sp.get_all_by_key::<#key, #svc>() }
+                        quote! { #sp.get_all_by_key::<#key, #svc>() }
                     }
                 } else if context.mutable {
-                    quote! { sp.get_all_by_key_mut::<#key, #svc>().collect() }
+                    quote! { #sp.get_all_by_key_mut::<#key, #svc>().collect() }
                 } else {
-                    quote! { sp.get_all_by_key::<#key, #svc>().collect() }
+                    quote! { #sp.get_all_by_key::<#key, #svc>().collect() }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -157,22 +162,22 @@ pub trait CallSiteInjector<'a> {
             }
         } else {
             InjectedCallSite {
-                resolve: if context.lazy {
+                resolve: if let Some(wrapper) = context.wrapper() {
                     if context.mutable {
-                        quote! { di::lazy::zero_or_more_mut::<#svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_more_mut", quote! { #svc })
                     } else {
-                        quote! { di::lazy::zero_or_more::<#svc>(sp.clone()) }
+                        wrapper.resolve("zero_or_more", quote! { #svc })
                     }
                 } else if context.iterator {
                     if context.mutable {
-                        quote! { sp.get_all_mut::<#svc>() }
+                        quote! { #sp.get_all_mut::<#svc>() }
                     } else {
-                        quote! { sp.get_all::<#svc>() }
+                        quote! { #sp.get_all::<#svc>() }
                     }
                 } else if context.mutable {
-                    quote! { sp.get_all_mut::<#svc>().collect() }
+                    quote! { #sp.try_get_all_mut::<#svc>()? }
                 } else {
-                    quote! { sp.get_all::<#svc>().collect() }
+                    quote! { #sp.try_get_all::<#svc>()? }
                 },
                 dependency: if context.mutable {
                     Some(quote! {
@@ -195,12 +200,21 @@ where
     T: CallSiteInjector<'a>,
 {
     fn inject(&self, context: &CallSiteContext) -> InjectedCallSite {
-        if context.optional {
+        let mut callsite = if context.optional {
             self.optional(context)
         } else if context.many {
             self.many(context)
         } else {
             self.required(context)
+        };
+
+        // an owned service is resolved within its own scope
+        if context.owned {
+            callsite.dependency = callsite
+                .dependency
+                .map(|dependency| quote! { #dependency.as_scope_boundary() });
         }
+
+        callsite
     }
 }
@@ -6,6 +6,7 @@ extern crate proc_macro;
 use crate::internal::*;
 use internal::{Constructor, DeriveContext, InjectableTrait};
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
@@ -51,13 +52,19 @@ fn _injectable(metadata: TokenStream, input: TokenStream) -> TokenStream {
 fn derive_from_struct_impl(
     impl_: ItemImpl,
     attribute: InjectableAttribute,
-    original: TokenStream,
+    mut original: TokenStream,
 ) -> Result<TokenStream> {
     if let Type::Path(type_) = &*impl_.self_ty {
         let imp = &type_.path;
         let svc = service_from_attribute(imp, attribute);
         match Constructor::select(&impl_, imp) {
             Ok(method) => {
+                if !Constructor::arguments(method).is_empty() {
+                    let mut stripped = impl_.clone();
+                    Constructor::strip_arguments(&mut stripped);
+                    original = stripped.into_token_stream();
+                }
+
                 let context = DeriveContext::for_method(&impl_.generics, imp, svc, method);
                 derive(context, original)
             }
@@ -172,9 +179,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new ()) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new () . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new ())) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new () . into ()))) , ",
             "lifetime) ",
             "} ",
             "}"
@@ -213,9 +220,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: create ()) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: create () . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: create ())) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: create () . into ()))) , ",
             "lifetime) ",
             "} ",
             "}"
@@ -252,9 +259,210 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new (sp . get_required :: < dyn Bar > ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new (sp . get_required :: < dyn Bar > ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ?))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ?) . into ()))) , ",
+            "lifetime) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_inject_factory_dependency() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Factory<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : Factory < Rc < dyn Bar >>) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
+            "di :: InjectBuilder :: new (",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (di :: factory :: exactly_one :: < dyn Bar > (sp . clone ())))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (di :: factory :: exactly_one :: < dyn Bar > (sp . clone ())) . into ()))) , ",
+            "lifetime) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_inject_owned_dependency() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Owned<Rc<dyn Bar>>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : Owned < Rc < dyn Bar >>) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
+            "di :: InjectBuilder :: new (",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (di :: owned :: try_exactly_one :: < dyn Bar > (sp . clone ()) ?))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (di :: owned :: try_exactly_one :: < dyn Bar > (sp . clone ()) ?) . into ()))) , ",
+            "lifetime) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne) . as_scope_boundary ()) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_implement_assisted_factory_for_runtime_arguments() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Rc<dyn Bar>, #[arg] id: u64) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : Rc < dyn Bar > , id : u64) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
+            "di :: InjectBuilder :: new (",
+            "di :: Activator :: new :: < di :: factory :: Factory < di :: Ref < dyn Foo > , (u64 ,) > , Self > (",
+            "| sp : & di :: ServiceProvider | di :: Ref :: new (di :: factory :: assisted (sp . clone () , ",
+            "| sp : & di :: ServiceProvider , (id ,) : (u64 ,) | -> Result < di :: Ref < dyn Foo > , di :: ResolveError > { ",
+            "Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ? , id))) ",
+            "})) , ",
+            "| sp : & di :: ServiceProvider | di :: RefMut :: new (di :: factory :: assisted (sp . clone () , ",
+            "| sp : & di :: ServiceProvider , (id ,) : (u64 ,) | -> Result < di :: Ref < dyn Foo > , di :: ResolveError > { ",
+            "Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ? , id))) ",
+            "}) . into ())) , ",
+            "lifetime) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_inject_assisted_factory_dependency() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bars: Factory<Ref<dyn Bar>, (u64,)>) -> Self {
+                    Self { }
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bars : Factory < Ref < dyn Bar >, (u64 ,) >) -> Self { ",
+            "Self { } ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
+            "di :: InjectBuilder :: new (",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new ((* sp . try_get_required :: < Factory < Ref < dyn Bar > , (u64 ,) > > () ?) . clone ()))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new ((* sp . try_get_required :: < Factory < Ref < dyn Bar > , (u64 ,) > > () ?) . clone ()) . into ()))) , ",
+            "lifetime) ",
+            ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < Factory < Ref < dyn Bar > , (u64 ,) > > () , di :: ServiceCardinality :: ExactlyOne)) ",
+            "} ",
+            "}");
+
+        assert_eq!(expected, result.to_string());
+    }
+
+    #[test]
+    fn attribute_should_propagate_error_from_fallible_constructor() {
+        // arrange
+        let metadata = TokenStream::from_str(r#"Foo"#).unwrap();
+        let input = TokenStream::from_str(
+            r#"
+            impl FooImpl {
+                fn new(_bar: Rc<dyn Bar>) -> Result<Self, Error> {
+                    Ok(Self { })
+                }
+            }
+        "#,
+        )
+        .unwrap();
+
+        // act
+        let result = _injectable(metadata, input);
+
+        // assert
+        let expected = concat!(
+            "impl FooImpl { ",
+            "fn new (_bar : Rc < dyn Bar >) -> Result < Self , Error > { ",
+            "Ok (Self { }) ",
+            "} ",
+            "} ",
+            "impl di :: Injectable for FooImpl { ",
+            "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
+            "di :: InjectBuilder :: new (",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ?) . map_err (di :: Activator :: error) ?)) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . try_get_required :: < dyn Bar > () ?) . map_err (di :: Activator :: error) ? . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
             "} ",
@@ -291,9 +499,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new (sp . get :: < dyn Bar > ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new (sp . get :: < dyn Bar > ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . get :: < dyn Bar > ()))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . get :: < dyn Bar > ()) . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrOne)) ",
             "} ",
@@ -330,9 +538,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new (sp . get_all :: < dyn Bar > () . collect ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new (sp . get_all :: < dyn Bar > () . collect ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . try_get_all :: < dyn Bar > () ?))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . try_get_all :: < dyn Bar > () ?) . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrMore)) ",
             "} ",
@@ -371,9 +579,9 @@ mod test {
             "impl di :: Injectable for ThingImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Thing , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: create_new (sp . get_required :: < dyn Foo > () , sp . get :: < dyn Bar > ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: create_new (sp . get_required :: < dyn Foo > () , sp . get :: < dyn Bar > ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Thing , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: create_new (sp . try_get_required :: < dyn Foo > () ? , sp . get :: < dyn Bar > ()))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: create_new (sp . try_get_required :: < dyn Foo > () ? , sp . get :: < dyn Bar > ()) . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Foo > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < dyn Bar > () , di :: ServiceCardinality :: ZeroOrOne)) ",
@@ -411,9 +619,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < Self , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new ()) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new () . into ())) , ",
+            "di :: Activator :: try_new :: < Self , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new ())) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new () . into ()))) , ",
             "lifetime) ",
             "} ",
             "}"
@@ -450,9 +658,9 @@ mod test {
             "impl di :: Injectable for FooImpl { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Foo , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new (sp . get_required :: < Bar > ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new (sp . get_required :: < Bar > ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Foo , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < Bar > () ?))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . try_get_required :: < Bar > () ?) . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < Bar > () , di :: ServiceCardinality :: ExactlyOne)) ",
             "} ",
@@ -489,9 +697,9 @@ mod test {
             "impl < T : Default > di :: Injectable for GenericBar < T > { ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < Self , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new ()) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new () . into ())) , ",
+            "di :: Activator :: try_new :: < Self , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new ())) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new () . into ()))) , ",
             "lifetime) ",
             "} ",
             "}"
@@ -540,9 +748,9 @@ mod test {
             "{ ",
             "fn inject (lifetime : di :: ServiceLifetime) -> di :: InjectBuilder { ",
             "di :: InjectBuilder :: new (",
-            "di :: Activator :: new :: < dyn Pair < TKey , TValue > , Self > (",
-            "| sp : & di :: ServiceProvider | di :: Ref :: new (Self :: new (sp . get_required :: < TKey > () , sp . get_required :: < TValue > ())) , ",
-            "| sp : & di :: ServiceProvider | di :: RefMut :: new (Self :: new (sp . get_required :: < TKey > () , sp . get_required :: < TValue > ()) . into ())) , ",
+            "di :: Activator :: try_new :: < dyn Pair < TKey , TValue > , Self > (",
+            "| sp : & di :: ServiceProvider | Ok (di :: Ref :: new (Self :: new (sp . try_get_required :: < TKey > () ? , sp . try_get_required :: < TValue > () ?))) , ",
+            "| sp : & di :: ServiceProvider | Ok (di :: RefMut :: new (Self :: new (sp . try_get_required :: < TKey > () ? , sp . try_get_required :: < TValue > () ?) . into ()))) , ",
             "lifetime) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < TKey > () , di :: ServiceCardinality :: ExactlyOne)) ",
             ". depends_on (di :: ServiceDependency :: new (di :: Type :: of :: < TValue > () , di :: ServiceCardinality :: ExactlyOne)) ",
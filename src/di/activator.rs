@@ -1,5 +1,8 @@
-use crate::{Mut, Ref, RefMut, ServiceFactory, ServiceProvider, Type};
+use crate::description::Activate;
+use crate::resolution::expect;
+use crate::{Mut, Ref, RefMut, ResolveError, ServiceFactory, ServiceProvider, Type};
 use std::any::Any;
+use std::error::Error;
 
 macro_rules! new {
     ($($traits:tt)+) => {
@@ -17,8 +20,32 @@ macro_rules! new {
                 service_type: Type::of::<TSvc>(),
                 service_type_mut: Type::of::<Mut<TSvc>>(),
                 implementation_type: Type::of::<TImpl>(),
-                factory: Ref::new(move |sp| Ref::new(factory(sp))),
-                factory_mut: Ref::new(move |sp| Ref::new(factory_mut(sp))),
+                factory: Ref::new(move |sp| Ok(Ref::new(factory(sp)))),
+                factory_mut: Ref::new(move |sp| Ok(Ref::new(factory_mut(sp)))),
+                mutable: false,
+            }
+        }
+
+        /// Creates a new activator using the specified fallible factory methods to instantiate the service.
+        ///
+        /// # Arguments
+        ///
+        /// * `factory` - The factory method used to create a service instance
+        /// * `factory_mut` - The factory method used to create a mutable service instance
+        ///
+        /// # Remarks
+        ///
+        /// If a factory method returns an error, the resolution of the service fails with the error.
+        pub fn try_new<TSvc: ?Sized + $($traits)+, TImpl>(
+            factory: fn(&ServiceProvider) -> Result<Ref<TSvc>, ResolveError>,
+            factory_mut: fn(&ServiceProvider) -> Result<RefMut<TSvc>, ResolveError>,
+        ) -> Self {
+            Self {
+                service_type: Type::of::<TSvc>(),
+                service_type_mut: Type::of::<Mut<TSvc>>(),
+                implementation_type: Type::of::<TImpl>(),
+                factory: Ref::new(move |sp| Ok(Ref::new(factory(sp)?))),
+                factory_mut: Ref::new(move |sp| Ok(Ref::new(factory_mut(sp)?))),
                 mutable: false,
             }
         }
@@ -30,8 +57,8 @@ pub struct Activator {
     service_type: Type,
     service_type_mut: Type,
     implementation_type: Type,
-    factory: Ref<ServiceFactory>,
-    factory_mut: Ref<ServiceFactory>,
+    factory: Ref<Activate>,
+    factory_mut: Ref<Activate>,
     mutable: bool,
 }
 
@@ -51,6 +78,11 @@ impl Activator {
         &self.implementation_type
     }
 
+    #[inline]
+    pub(crate) fn is_mutable(&self) -> bool {
+        self.mutable
+    }
+
     /// Sets a value indicating whether the activated instance should be mutable.
     #[inline]
     pub fn as_mut(&mut self) {
@@ -58,7 +90,16 @@ impl Activator {
     }
 
     /// Gets the [factory](ServiceFactory) method the activator represents.
+    ///
+    /// # Remarks
+    ///
+    /// The factory method panics if the service cannot be activated.
     pub fn factory(&self) -> Ref<ServiceFactory> {
+        let activate = self.activate();
+        Ref::new(move |sp| expect(activate(sp)))
+    }
+
+    pub(crate) fn activate(&self) -> Ref<Activate> {
         if self.mutable {
             self.factory_mut.clone()
         } else {
@@ -66,6 +107,12 @@ impl Activator {
         }
     }
 
+    // used by the proc macro to fail the activation of an instance from a constructor that returns a Result
+    #[doc(hidden)]
+    pub fn error<E: Into<Box<dyn Error + Send + Sync>>>(error: E) -> ResolveError {
+        ResolveError::factory(error.into())
+    }
+
     cfg_if::cfg_if! {
         if #[cfg(feature = "async")] {
             new!(Any + Send + Sync);
@@ -1,6 +1,10 @@
+use crate::description::Activate;
 use crate::{
-    Ref, ServiceCardinality::*, ServiceDependency, ServiceDescriptor, ServiceDescriptorBuilder, ServiceLifetime::*,
-    ServiceProvider, Type,
+    OpenGeneric, Ref, ResolveError,
+    ServiceCardinality::{self, *},
+    ServiceDependency, ServiceDescriptor, ServiceDescriptorBuilder,
+    ServiceLifetime::{self, *},
+    ServiceProvider, Type, ValueKey,
 };
 use std::any::Any;
 use std::mem::MaybeUninit;
@@ -11,8 +15,83 @@ type Sdb<TSvc, TImpl> = ServiceDescriptorBuilder<TSvc, TImpl>;
 macro_rules! service_from_type {
     ($($traits:tt)+) => {
         #[inline(always)]
-        fn no_op(_: &ServiceProvider) -> Ref<dyn $($traits)+> {
-            Ref::new(MaybeUninit::<Box<dyn $($traits)+>>::uninit())
+        fn no_op(_: &ServiceProvider) -> Result<Ref<dyn $($traits)+>, ResolveError> {
+            Ok(Ref::new(MaybeUninit::<Box<dyn $($traits)+>>::uninit()))
+        }
+
+        fn open_generic<T: ?Sized + OpenGeneric>(lifetime: ServiceLifetime) -> ServiceDescriptor {
+            let definition = Type::definition_of::<T>();
+
+            ServiceDescriptor::new(
+                lifetime,
+                definition.clone(),
+                definition,
+                T::dependencies(),
+                OnceLock::new(),
+                Ref::new(no_op),
+            )
+        }
+
+        /// Creates a new singleton [ServiceDescriptor] for an open generic service.
+        ///
+        /// # Remarks
+        ///
+        /// The generic type definition is determined by the [OpenGeneric] implementation of `T`, which can be any
+        /// closed type of the generic service. By convention, `()` is used for each type argument. Each closed type is
+        /// activated by its [OpenGeneric] implementation and has its own singleton instance.
+        #[inline]
+        pub fn singleton_generic<T: ?Sized + OpenGeneric + $($traits)+>() -> ServiceDescriptor {
+            open_generic::<T>(Singleton)
+        }
+
+        /// Creates a new scoped [ServiceDescriptor] for an open generic service.
+        ///
+        /// # Remarks
+        ///
+        /// Refer to [singleton_generic] for more information.
+        #[inline]
+        pub fn scoped_generic<T: ?Sized + OpenGeneric + $($traits)+>() -> ServiceDescriptor {
+            open_generic::<T>(Scoped)
+        }
+
+        /// Creates a new transient [ServiceDescriptor] for an open generic service.
+        ///
+        /// # Remarks
+        ///
+        /// Refer to [singleton_generic] for more information.
+        #[inline]
+        pub fn transient_generic<T: ?Sized + OpenGeneric + $($traits)+>() -> ServiceDescriptor {
+            open_generic::<T>(Transient)
+        }
+
+        fn closed_generic<T: ?Sized + OpenGeneric + $($traits)+>(cardinality: ServiceCardinality) -> ServiceDependency {
+            let injected_type = Type::of::<T>();
+            let definition = Type::definition_of::<T>();
+            let activate: Ref<Activate> = Ref::new(|sp| Ok(Ref::new(T::activate(sp))));
+
+            ServiceDependency::new(injected_type, cardinality).closed_by(definition, activate)
+        }
+
+        /// Creates a new [ServiceDependency] on a closed generic type with a cardinality of exactly one (1:1).
+        ///
+        /// # Remarks
+        ///
+        /// The dependency is satisfied by a registration of the closed type or, otherwise, by the registration of its
+        /// open generic type definition. Once a [ServiceProvider] is built, the closed type can be resolved as any
+        /// other service, such as with [get_required](ServiceProvider::get_required).
+        #[inline]
+        pub fn exactly_one_generic<T: ?Sized + OpenGeneric + $($traits)+>() -> ServiceDependency {
+            closed_generic::<T>(ExactlyOne)
+        }
+
+        /// Creates a new [ServiceDependency] on a closed generic type with a cardinality of zero or one (0:1).
+        ///
+        /// # Remarks
+        ///
+        /// Refer to [exactly_one_generic] for more information.
+        #[inline]
+        pub fn zero_or_one_generic<T: ?Sized + OpenGeneric + $($traits)+>() -> ServiceDependency {
+            closed_generic::<T>(ZeroOrOne)
         }
 
         /// Initializes a new singleton [ServiceDescriptorBuilder].
@@ -27,6 +106,18 @@ macro_rules! service_from_type {
             Sdb::keyed::<TKey>(Singleton, Type::of::<TImpl>())
         }
 
+        /// Initializes a new singleton [ServiceDescriptorBuilder] keyed by a runtime value.
+        ///
+        /// # Arguments
+        ///
+        /// * `key` - The value used as the key of the service
+        #[inline]
+        pub fn singleton_with_value_key<TSvc: ?Sized + $($traits)+, TImpl>(
+            key: impl ValueKey,
+        ) -> ServiceDescriptorBuilder<TSvc, TImpl> {
+            Sdb::value_keyed(Singleton, Type::of::<TImpl>(), &key)
+        }
+
         /// Initializes a new singleton [ServiceDescriptorBuilder].
         ///
         /// # Remarks
@@ -49,6 +140,18 @@ macro_rules! service_from_type {
             Sdb::keyed::<TKey>(Scoped, Type::of::<TImpl>())
         }
 
+        /// Initializes a new scoped [ServiceDescriptorBuilder] keyed by a runtime value.
+        ///
+        /// # Arguments
+        ///
+        /// * `key` - The value used as the key of the service
+        #[inline]
+        pub fn scoped_with_value_key<TSvc: ?Sized + $($traits)+, TImpl>(
+            key: impl ValueKey,
+        ) -> ServiceDescriptorBuilder<TSvc, TImpl> {
+            Sdb::value_keyed(Scoped, Type::of::<TImpl>(), &key)
+        }
+
         /// Initializes a new transient [ServiceDescriptorBuilder].
         #[inline]
         pub fn transient<TSvc: ?Sized + $($traits)+, TImpl>() -> ServiceDescriptorBuilder<TSvc, TImpl> {
@@ -61,6 +164,18 @@ macro_rules! service_from_type {
             Sdb::keyed::<TKey>(Transient, Type::of::<TImpl>())
         }
 
+        /// Initializes a new transient [ServiceDescriptorBuilder] keyed by a runtime value.
+        ///
+        /// # Arguments
+        ///
+        /// * `key` - The value used as the key of the service
+        #[inline]
+        pub fn transient_with_value_key<TSvc: ?Sized + $($traits)+, TImpl>(
+            key: impl ValueKey,
+        ) -> ServiceDescriptorBuilder<TSvc, TImpl> {
+            Sdb::value_keyed(Transient, Type::of::<TImpl>(), &key)
+        }
+
         /// Initializes a new transient [ServiceDescriptorBuilder].
         ///
         /// # Remarks
@@ -271,6 +386,16 @@ pub fn exactly_one_with_key<TKey, TSvc: Any + ?Sized>() -> ServiceDependency {
     ServiceDependency::new(Type::keyed::<TKey, TSvc>(), ExactlyOne)
 }
 
+/// Creates a new [ServiceDependency] keyed by a runtime value with a cardinality of exactly one (1:1).
+///
+/// # Arguments
+///
+/// * `key` - The value used as the key of the dependency
+#[inline]
+pub fn exactly_one_with_value_key<TSvc: Any + ?Sized>(key: impl ValueKey) -> ServiceDependency {
+    ServiceDependency::new(Type::value_keyed::<TSvc>(&key), ExactlyOne)
+}
+
 /// Creates a new [ServiceDependency] with a cardinality of zero or one (0:1).
 #[inline]
 pub fn zero_or_one<T: Any + ?Sized>() -> ServiceDependency {
@@ -283,6 +408,16 @@ pub fn zero_or_one_with_key<TKey, TSvc: Any + ?Sized>() -> ServiceDependency {
     ServiceDependency::new(Type::keyed::<TKey, TSvc>(), ZeroOrOne)
 }
 
+/// Creates a new [ServiceDependency] keyed by a runtime value with a cardinality of zero or one (0:1).
+///
+/// # Arguments
+///
+/// * `key` - The value used as the key of the dependency
+#[inline]
+pub fn zero_or_one_with_value_key<TSvc: Any + ?Sized>(key: impl ValueKey) -> ServiceDependency {
+    ServiceDependency::new(Type::value_keyed::<TSvc>(&key), ZeroOrOne)
+}
+
 /// Creates a new [ServiceDependency] with a cardinality of zero or more (0:*).
 #[inline]
 pub fn zero_or_more<T: Any + ?Sized>() -> ServiceDependency {
@@ -294,3 +429,13 @@ pub fn zero_or_more<T: Any + ?Sized>() -> ServiceDependency {
 pub fn zero_or_more_with_key<TKey, TSvc: Any + ?Sized>() -> ServiceDependency {
     ServiceDependency::new(Type::keyed::<TKey, TSvc>(), ZeroOrMore)
 }
+
+/// Creates a new [ServiceDependency] keyed by a runtime value with a cardinality of zero or more (0:*).
+///
+/// # Arguments
+///
+/// * `key` - The value used as the key of the dependency
+#[inline]
+pub fn zero_or_more_with_value_key<TSvc: Any + ?Sized>(key: impl ValueKey) -> ServiceDependency {
+    ServiceDependency::new(Type::value_keyed::<TSvc>(&key), ZeroOrMore)
+}
@@ -1,4 +1,7 @@
-use crate::{fmt, validate, Ref, ServiceDescriptor, ServiceDescriptorBuilder, ServiceProvider, Type, ValidationError};
+use crate::{
+    eager, fmt, hosted, validate_with, Ref, ResolveError, ServiceDescriptor, ServiceDescriptorBuilder, ServiceProvider,
+    Type, ValidationError, ValidationOptions,
+};
 use std::any::Any;
 use std::collections::HashMap;
 use std::fmt::{Formatter, Result as FormatResult};
@@ -80,9 +83,9 @@ macro_rules! decorate {
                 let original = item.clone();
                 let builder = ServiceDescriptorBuilder::<TSvc, TImpl>::new(original.lifetime(), impl_type);
 
-                *item = builder.from(move |sp| {
-                    let decorated = original.get(sp).downcast_ref::<Ref<TSvc>>().unwrap().clone();
-                    activate(sp, decorated)
+                *item = builder.try_from(move |sp| {
+                    let decorated = original.try_get(sp)?.downcast_ref::<Ref<TSvc>>().unwrap().clone();
+                    Ok::<_, ResolveError>(activate(sp, decorated))
                 });
 
                 break;
@@ -191,9 +194,9 @@ macro_rules! decorate {
                 let activate = func.clone();
                 let builder = ServiceDescriptorBuilder::<TSvc, TImpl>::new(original.lifetime(), impl_type);
 
-                *item = builder.from(move |sp| {
-                    let decorated = original.get(sp).downcast_ref::<Ref<TSvc>>().unwrap().clone();
-                    (activate)(sp, decorated)
+                *item = builder.try_from(move |sp| {
+                    let decorated = original.try_get(sp)?.downcast_ref::<Ref<TSvc>>().unwrap().clone();
+                    Ok::<_, ResolveError>((activate)(sp, decorated))
                 });
             }
 
@@ -358,19 +361,153 @@ impl ServiceCollection {
     }
 
     /// Builds and returns a new [ServiceProvider].
+    #[inline]
     pub fn build_provider(&self) -> Result<ServiceProvider, ValidationError> {
-        validate(self)?;
+        self.build_provider_with(ValidationOptions::default())
+    }
 
+    /// Builds and returns a new, strict [ServiceProvider].
+    ///
+    /// # Remarks
+    ///
+    /// A strict provider records every service that is resolved while another service is activated. Any service that
+    /// is not one of the declared [dependencies](crate::ServiceDependency) of the service being activated is reported
+    /// by [undeclared_dependencies](ServiceProvider::undeclared_dependencies). A strict provider is intended to verify
+    /// registrations, typically in a test, and has additional overhead for each resolution.
+    #[inline]
+    pub fn build_strict_provider(&self) -> Result<ServiceProvider, ValidationError> {
+        self.build_provider_with(ValidationOptions::default().strict(true))
+    }
+
+    /// Builds and returns a new [ServiceProvider] using the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `options` - The [validation options](ValidationOptions) used to build the provider
+    ///
+    /// # Remarks
+    ///
+    /// Any singletons that the options activate eagerly are activated after the provider is built. A singleton that
+    /// fails to activate is reported by the returned [error](ValidationError).
+    pub fn build_provider_with(&self, options: ValidationOptions) -> Result<ServiceProvider, ValidationError> {
+        validate_with(self, &options)?;
+
+        let options = Ref::new(options);
+        let provider = ServiceProvider::with_options(self.build(options.is_strict()), options.clone());
+
+        eager::activate(self, &provider, options.activation(), options.is_parallel())?;
+        Ok(provider)
+    }
+
+    /// Exports the services in the collection as a [Graphviz](https://graphviz.org) DOT graph.
+    ///
+    /// # Remarks
+    ///
+    /// Each registered service is a node labeled with its service type, key, implementation type, and lifetime. Each
+    /// dependency is an edge labeled with its [cardinality](crate::ServiceCardinality). Missing services, circular
+    /// dependencies, and scoped services that are reachable from a singleton are highlighted the same way they are
+    /// when the collection is displayed.
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+        fmt::dot::write(self, &mut output).unwrap();
+        output
+    }
+
+    /// Exports the services in the collection as a [Mermaid](https://mermaid.js.org) flowchart.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [to_dot](Self::to_dot) for more information.
+    pub fn to_mermaid(&self) -> String {
+        let mut output = String::new();
+        fmt::mermaid::write(self, &mut output).unwrap();
+        output
+    }
+
+    /// Renders the services in the collection as a tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The [renderer](fmt::Renderer) used to render the services
+    ///
+    /// # Remarks
+    ///
+    /// This is the same tree used to [display](std::fmt::Display) the collection.
+    #[inline]
+    pub fn render(&self, renderer: &mut impl fmt::Renderer) -> FormatResult {
+        self.render_with(renderer, &fmt::RenderOptions::default())
+    }
+
+    /// Renders the services in the collection as a tree using the specified options.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The [renderer](fmt::Renderer) used to render the services
+    /// * `options` - The [render options](fmt::RenderOptions) used to render the services
+    pub fn render_with(&self, renderer: &mut impl fmt::Renderer, options: &fmt::RenderOptions) -> FormatResult {
+        fmt::write(self, renderer, options)
+    }
+
+    /// Explains how the specified service is resolved.
+    ///
+    /// # Remarks
+    ///
+    /// The [explanation](fmt::Explanation) only includes the dependency tree that is reachable from the specified
+    /// service, which is much easier to follow than the entire collection. Each service is annotated with its
+    /// effective [lifetime](crate::ServiceLifetime) and the tree is followed by any validation errors and warnings for
+    /// the services it contains.
+    #[inline]
+    pub fn explain<T: Any + ?Sized>(&self) -> fmt::Explanation<'_> {
+        fmt::Explanation::new(self, Type::of::<T>())
+    }
+
+    /// Explains how the specified service with a key is resolved.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [explain](Self::explain) for more information.
+    #[inline]
+    pub fn explain_by_key<TKey, TSvc: Any + ?Sized>(&self) -> fmt::Explanation<'_> {
+        fmt::Explanation::new(self, Type::keyed::<TKey, TSvc>())
+    }
+
+    /// Creates a [manifest](crate::Manifest) of the services in the collection.
+    ///
+    /// # Remarks
+    ///
+    /// The manifest includes the results of validating the services with the default
+    /// [validation options](ValidationOptions). Use [Manifest::new](crate::Manifest::new) to specify other options.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn manifest(&self) -> crate::Manifest {
+        crate::Manifest::new(self, &ValidationOptions::default())
+    }
+
+    /// Exports a [manifest](crate::Manifest) of the services in the collection as JSON.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [manifest](Self::manifest) for more information.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.manifest()).unwrap()
+    }
+
+    pub(crate) fn build(&self, dependencies: bool) -> HashMap<Type, Vec<ServiceDescriptor>> {
         let mut services = HashMap::with_capacity(self.items.len());
+        let hosted = hosted::retained(self);
 
         for item in &self.items {
             let key = item.service_type().clone();
             let descriptors = services.entry(key).or_insert_with(Vec::new);
 
             // dependencies are only interesting for validation. after a ServiceProvider is created, no further
-            // validation occurs. prevent copying unnecessary memory and allow it to potentially be freed if the
-            // ServiceCollection is dropped.
-            descriptors.push(item.clone_with(false));
+            // validation occurs unless it is strict. prevent copying unnecessary memory and allow it to potentially be
+            // freed if the ServiceCollection is dropped. the dependencies of hosted services are the exception because
+            // they determine the order in which the services are started. a dependency on a closed generic type is
+            // always retained because it is how the provider closes the type.
+            descriptors.push(item.clone_with(dependencies || hosted.contains(item.service_type())));
         }
 
         for values in services.values_mut() {
@@ -378,7 +515,7 @@ impl ServiceCollection {
         }
 
         services.shrink_to_fit();
-        Ok(ServiceProvider::new(services))
+        services
     }
 
     /// Gets a read-only iterator for the collection
@@ -433,7 +570,7 @@ impl Index<usize> for ServiceCollection {
 
 impl std::fmt::Debug for ServiceCollection {
     fn fmt(&self, f: &mut Formatter<'_>) -> FormatResult {
-        fmt::write(self, fmt::text::Renderer, f)
+        self.render(&mut fmt::text::Renderer::new(f))
     }
 }
 
@@ -442,19 +579,19 @@ impl std::fmt::Display for ServiceCollection {
         cfg_if::cfg_if! {
             if #[cfg(feature = "fmt")] {
                 if f.alternate() {
-                    return fmt::write(self, fmt::terminal::Renderer, f);
+                    return self.render(&mut fmt::terminal::Renderer::new(f));
                 }
             }
         }
 
-        fmt::write(self, fmt::text::Renderer, f)
+        self.render(&mut fmt::text::Renderer::new(f))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{existing, existing_as_self, singleton, singleton_as_self, test::*, transient};
+    use crate::{exactly_one, existing, existing_as_self, scoped, singleton, singleton_as_self, test::*, transient};
     use std::fs::remove_file;
     use std::path::{Path, PathBuf};
 
@@ -592,7 +729,7 @@ mod tests {
         ];
         let mut collection = ServiceCollection::new();
 
-        collection.try_add_all(descriptors.into_iter());
+        collection.try_add_all(descriptors);
 
         // act
         let count = collection.len();
@@ -706,4 +843,202 @@ mod tests {
         remove_file(&file).ok();
         assert!(not_dropped);
     }
+
+    fn new_activation_services(log: &ActivationLog) -> ServiceCollection {
+        let (other, test) = (log.clone(), log.clone());
+        let mut services = ServiceCollection::new();
+
+        // the dependent singleton is registered before its dependency
+        services
+            .add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(move |sp| {
+                        other.activated("other");
+                        Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))
+                    }),
+            )
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(move |_| {
+                test.activated("test");
+                Ref::new(TestServiceImpl::default())
+            }));
+
+        services
+    }
+
+    #[test]
+    fn build_provider_should_not_activate_singletons_by_default() {
+        // arrange
+        let log = ActivationLog::default();
+        let services = new_activation_services(&log);
+
+        // act
+        let _ = services.build_provider().unwrap();
+
+        // assert
+        assert!(log.order().is_empty());
+    }
+
+    #[test]
+    fn build_provider_with_should_activate_singletons_in_dependency_order() {
+        // arrange
+        let log = ActivationLog::default();
+        let services = new_activation_services(&log);
+
+        // act
+        let provider = services
+            .build_provider_with(ValidationOptions::default().activate_singletons(true))
+            .unwrap();
+
+        // assert
+        let _ = provider.get_required::<dyn OtherTestService>();
+        assert_eq!(log.order(), vec!["test", "other"]);
+    }
+
+    #[test]
+    fn build_provider_with_should_only_activate_specified_singletons() {
+        // arrange
+        let log = ActivationLog::default();
+        let services = new_activation_services(&log);
+
+        // act
+        let _ = services
+            .build_provider_with(ValidationOptions::default().activate::<dyn TestService>())
+            .unwrap();
+
+        // assert
+        assert_eq!(log.order(), vec!["test"]);
+    }
+
+    #[test]
+    fn build_provider_with_should_activate_dependencies_of_specified_singletons() {
+        // arrange
+        let log = ActivationLog::default();
+        let services = new_activation_services(&log);
+
+        // act
+        let _ = services
+            .build_provider_with(ValidationOptions::default().activate::<dyn OtherTestService>())
+            .unwrap();
+
+        // assert
+        assert_eq!(log.order(), vec!["test", "other"]);
+    }
+
+    #[test]
+    fn build_provider_with_should_report_every_singleton_that_fails_to_activate() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(singleton::<dyn TestService, TestServiceImpl>().try_from(|_| Err("Test service failed.")))
+            .add(singleton::<dyn AnotherTestService, AnotherTestServiceImpl>().try_from(|_| Err("Another failed.")));
+
+        // act
+        let result = services.build_provider_with(ValidationOptions::default().activate_singletons(true));
+
+        // assert
+        let error = result.err().unwrap();
+        assert_eq!(error.results().len(), 2);
+        assert!(error
+            .results()
+            .iter()
+            .all(|result| result.rule() == "SingletonActivation"));
+        assert!(error.results()[0].message().ends_with("Test service failed."));
+    }
+
+    #[test]
+    fn build_provider_with_should_report_singleton_that_captures_scoped_service() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>().try_from(|sp| {
+                    Ok::<_, ResolveError>(Ref::new(OtherTestServiceImpl::new(
+                        sp.try_get_required::<dyn TestService>()?,
+                    )))
+                }),
+            );
+
+        // act
+        let result = services.build_provider_with(
+            ValidationOptions::default()
+                .validate_scopes(true)
+                .activate_singletons(true),
+        );
+
+        // assert
+        let error = result.err().unwrap();
+        assert_eq!(error.results().len(), 1);
+        assert!(error.results()[0]
+            .message()
+            .contains("cannot be resolved for the singleton service"));
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn build_provider_with_should_activate_singletons_in_parallel() {
+        // arrange
+        let log = ActivationLog::default();
+        let mut services = new_activation_services(&log);
+        let another = log.clone();
+
+        services.add(
+            singleton::<dyn AnotherTestService, AnotherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn OtherTestService>())
+                .from(move |sp| {
+                    another.activated("another");
+                    Ref::new(AnotherTestServiceImpl::new(sp.get_required::<dyn OtherTestService>()))
+                }),
+        );
+
+        // act
+        let _ = services
+            .build_provider_with(
+                ValidationOptions::default()
+                    .activate_singletons(true)
+                    .activate_in_parallel(true),
+            )
+            .unwrap();
+
+        // assert
+        assert_eq!(log.order(), vec!["test", "other", "another"]);
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn build_provider_with_should_activate_independent_singletons_on_separate_threads() {
+        // arrange
+        let log = ActivationLog::default();
+        let mut services = new_activation_services(&log);
+        let threads = Ref::new(std::sync::Mutex::new(std::collections::HashSet::new()));
+        let (first, second) = (threads.clone(), threads.clone());
+
+        // neither singleton has a dependency so both are activated in the first level
+        services
+            .add(singleton_as_self::<TestServiceImpl>().from(move |_| {
+                first.lock().unwrap().insert(std::thread::current().id());
+                Ref::new(TestServiceImpl::default())
+            }))
+            .add(singleton_as_self::<OtherTestServiceImpl>().from(move |_| {
+                second.lock().unwrap().insert(std::thread::current().id());
+                Ref::new(OtherTestServiceImpl::new(Ref::new(TestServiceImpl::default())))
+            }));
+
+        // act
+        let _ = services
+            .build_provider_with(
+                ValidationOptions::default()
+                    .activate_singletons(true)
+                    .activate_in_parallel(true),
+            )
+            .unwrap();
+
+        // assert
+        let parallel = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get) > 1;
+        assert_eq!(log.order().last(), Some(&"other"));
+        assert_eq!(threads.lock().unwrap().len(), if parallel { 2 } else { 1 });
+    }
 }
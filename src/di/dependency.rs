@@ -1,7 +1,10 @@
-use crate::Type;
+use crate::description::Activate;
+use crate::{Ref, ServiceProvider, Type};
+use std::fmt::{Debug, Formatter, Result as FormatResult};
 
 /// Represents the possible cardinalities of a service dependency.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ServiceCardinality {
     /// Indicates a cardinality of zero or one (0:1).
     ZeroOrOne,
@@ -14,10 +17,13 @@ pub enum ServiceCardinality {
 }
 
 /// Represents a service dependency.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone)]
 pub struct ServiceDependency {
     injected_type: Type,
     cardinality: ServiceCardinality,
+    scope_boundary: bool,
+    definition: Option<Type>,
+    activate: Option<Ref<Activate>>,
 }
 
 impl ServiceDependency {
@@ -31,9 +37,30 @@ impl ServiceDependency {
         Self {
             injected_type,
             cardinality,
+            scope_boundary: false,
+            definition: None,
+            activate: None,
         }
     }
 
+    // a dependency on a closed generic type is satisfied by the registration of its open generic type definition
+    pub(crate) fn closed_by(mut self, definition: Type, activate: Ref<Activate>) -> Self {
+        self.definition = Some(definition);
+        self.activate = Some(activate);
+        self
+    }
+
+    /// Indicates that the service dependency is resolved within its own scope.
+    ///
+    /// # Remarks
+    ///
+    /// A dependency resolved within its own scope, such as [Owned](crate::owned::Owned), is a scope boundary. The
+    /// scoped services it requires belong to its scope rather than the scope of the dependent service.
+    pub fn as_scope_boundary(mut self) -> Self {
+        self.scope_boundary = true;
+        self
+    }
+
     /// Gets the [injected type](Type) associated with the service dependency.
     #[inline]
     pub fn injected_type(&self) -> &Type {
@@ -45,4 +72,53 @@ impl ServiceDependency {
     pub fn cardinality(&self) -> ServiceCardinality {
         self.cardinality
     }
+
+    // the service provider is always available to a service even though it is never registered
+    #[inline]
+    pub(crate) fn is_provided(&self) -> bool {
+        self.injected_type == Type::of::<ServiceProvider>()
+    }
+
+    /// Gets a value indicating whether the service dependency is resolved within its own scope.
+    #[inline]
+    pub fn is_scope_boundary(&self) -> bool {
+        self.scope_boundary
+    }
+
+    /// Gets the open generic type definition that the service dependency can be satisfied by, if any.
+    ///
+    /// # Remarks
+    ///
+    /// Only a dependency declared on a closed generic type with a type that implements
+    /// [OpenGeneric](crate::OpenGeneric) has a generic type definition.
+    #[inline]
+    pub fn definition(&self) -> Option<&Type> {
+        self.definition.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn activator(&self) -> Option<&Ref<Activate>> {
+        self.activate.as_ref()
+    }
+}
+
+impl PartialEq for ServiceDependency {
+    fn eq(&self, other: &Self) -> bool {
+        self.injected_type == other.injected_type
+            && self.cardinality == other.cardinality
+            && self.scope_boundary == other.scope_boundary
+            && self.definition == other.definition
+    }
+}
+
+impl Debug for ServiceDependency {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        formatter
+            .debug_struct("ServiceDependency")
+            .field("injected_type", &self.injected_type)
+            .field("cardinality", &self.cardinality)
+            .field("scope_boundary", &self.scope_boundary)
+            .field("definition", &self.definition)
+            .finish()
+    }
 }
@@ -2,6 +2,7 @@ mod descriptor;
 mod lifetime;
 
 pub use descriptor::ServiceDescriptor;
+pub(crate) use descriptor::{Activate, Instance};
 pub use lifetime::ServiceLifetime;
 
 cfg_if::cfg_if! {
@@ -1,6 +1,10 @@
-use super::{ServiceDescriptor, ServiceLifetime};
-use crate::{Ref, ServiceDependency, ServiceProvider, Type};
+use super::{Activate, ServiceDescriptor, ServiceLifetime};
+use crate::{
+    disposable::{tracker, Tracker},
+    Disposable, Ref, ResolveError, ServiceDependency, ServiceProvider, Type, ValueKey,
+};
 use std::any::Any;
+use std::error::Error;
 use std::marker::PhantomData;
 use std::sync::OnceLock;
 
@@ -10,6 +14,7 @@ pub struct ServiceDescriptorBuilder<TSvc: ?Sized, TImpl> {
     service_type: Type,
     implementation_type: Type,
     dependencies: Vec<ServiceDependency>,
+    tracker: Option<Tracker>,
     _marker_svc: PhantomData<TSvc>,
     _marker_impl: PhantomData<TImpl>,
 }
@@ -39,6 +44,7 @@ impl<TSvc: ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
             service_type: Type::of::<TSvc>(),
             implementation_type,
             dependencies: Vec::new(),
+            tracker: None,
             _marker_svc: PhantomData,
             _marker_impl: PhantomData,
         }
@@ -56,6 +62,26 @@ impl<TSvc: ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
             service_type: Type::keyed::<TKey, TSvc>(),
             implementation_type,
             dependencies: Vec::new(),
+            tracker: None,
+            _marker_svc: PhantomData,
+            _marker_impl: PhantomData,
+        }
+    }
+
+    /// Initializes a new service descriptor builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The [lifetime](ServiceLifetime) of the service
+    /// * `implementation_type` - The service implementation [type](Type)
+    /// * `key` - The runtime value used as the key of the service
+    pub fn value_keyed(lifetime: ServiceLifetime, implementation_type: Type, key: &(impl ValueKey + ?Sized)) -> Self {
+        Self {
+            lifetime,
+            service_type: Type::value_keyed::<TSvc>(key),
+            implementation_type,
+            dependencies: Vec::new(),
+            tracker: None,
             _marker_svc: PhantomData,
             _marker_impl: PhantomData,
         }
@@ -65,12 +91,57 @@ impl<TSvc: ?Sized, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
 macro_rules! from {
     (($($traits:tt)+), ($($bounds:tt)+)) => {
         impl<TSvc: ?Sized + $($traits)+, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
+            /// Indicates the service is [disposable](Disposable).
+            ///
+            /// # Remarks
+            ///
+            /// Every instance activated by the factory function is tracked by the owning [ServiceProvider] and
+            /// disposed, in the reverse order of creation, when the provider is dropped or
+            /// [disposed](ServiceProvider::dispose).
+            pub fn disposable(mut self) -> Self
+            where
+                TSvc: Disposable,
+            {
+                self.tracker = Some(tracker::<TSvc>());
+                self
+            }
+
             /// Defines the factory function used to activate the service and returns the corresponding [ServiceDescriptor].
             ///
             /// # Arguments
             ///
             /// * `factory` - The factory function used to activate the service
-            pub fn from(mut self, factory: impl (Fn(&ServiceProvider) -> Ref<TSvc>) + $($bounds)+) -> ServiceDescriptor {
+            pub fn from(self, factory: impl (Fn(&ServiceProvider) -> Ref<TSvc>) + $($bounds)+) -> ServiceDescriptor {
+                self.build(Ref::new(move |sp| Ok(Ref::new(factory(sp)))))
+            }
+
+            /// Defines the fallible factory function used to activate the service and returns the corresponding
+            /// [ServiceDescriptor].
+            ///
+            /// # Arguments
+            ///
+            /// * `factory` - The factory function used to activate the service
+            ///
+            /// # Remarks
+            ///
+            /// If the factory function returns an error, resolving the service fails with [ResolveError::Factory].
+            /// The error is returned by fallible resolution functions such as
+            /// [try_get_required](ServiceProvider::try_get_required); otherwise, it panics. A [ResolveError] returned
+            /// by the factory function, such as the failure to resolve one of its dependencies, is returned as is.
+            pub fn try_from<E>(
+                self,
+                factory: impl (Fn(&ServiceProvider) -> Result<Ref<TSvc>, E>) + $($bounds)+,
+            ) -> ServiceDescriptor
+            where
+                E: Into<Box<dyn Error + Send + Sync>>,
+            {
+                self.build(Ref::new(move |sp| match factory(sp) {
+                    Ok(service) => Ok(Ref::new(service)),
+                    Err(error) => Err(ResolveError::factory(error.into())),
+                }))
+            }
+
+            fn build(mut self, factory: Ref<Activate>) -> ServiceDescriptor {
                 ServiceDescriptor::new(
                     self.lifetime,
                     self.service_type,
@@ -82,8 +153,9 @@ macro_rules! from {
                         self.dependencies
                     },
                     OnceLock::new(),
-                    Ref::new(move |sp| Ref::new(factory(sp))),
+                    factory,
                 )
+                .tracked_by(self.tracker)
             }
         }
     }
@@ -96,3 +168,21 @@ cfg_if::cfg_if! {
         from!((Any), ('static));
     }
 }
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+impl<TSvc: ?Sized + Any + Send + Sync, TImpl> ServiceDescriptorBuilder<TSvc, TImpl> {
+    /// Indicates the service is [asynchronously disposable](crate::AsyncDisposable).
+    ///
+    /// # Remarks
+    ///
+    /// Every instance activated by the factory function is tracked by the owning [ServiceProvider] and disposed,
+    /// in the reverse order of creation, when [dispose_async](ServiceProvider::dispose_async) is awaited.
+    pub fn async_disposable(mut self) -> Self
+    where
+        TSvc: crate::AsyncDisposable,
+    {
+        self.tracker = Some(crate::disposable::async_tracker::<TSvc>());
+        self
+    }
+}
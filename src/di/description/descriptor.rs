@@ -1,17 +1,75 @@
 use super::ServiceLifetime::{self, *};
-use crate::{Ref, ServiceDependency, ServiceFactory, ServiceProvider, Type};
+use crate::{
+    disposable::Tracker,
+    resolution::{self, Activation},
+    Ref, ResolutionEvent, ResolveError, ServiceDependency, ServiceProvider, Type,
+};
 use std::any::Any;
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock, PoisonError};
+use std::time::Instant;
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "async")] {
         macro_rules! service {
             () => { dyn Any + Send + Sync };
         }
+
+        /// Represents the fallible function used to activate a service.
+        pub(crate) type Activate = dyn (Fn(&ServiceProvider) -> Result<Ref<service!()>, ResolveError>) + Send + Sync;
     } else {
         macro_rules! service {
             () => { dyn Any };
         }
+
+        /// Represents the fallible function used to activate a service.
+        pub(crate) type Activate = dyn Fn(&ServiceProvider) -> Result<Ref<service!()>, ResolveError>;
+    }
+}
+
+/// Represents the instance cache of a service.
+#[derive(Default)]
+pub(crate) struct Instance {
+    value: OnceLock<Ref<service!()>>,
+
+    // a OnceLock cannot be initialized by a function that fails so activation is
+    // serialized by a lock and the value is only set when activation succeeds
+    lock: Mutex<()>,
+}
+
+impl Instance {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&Ref<service!()>> {
+        self.value.get()
+    }
+
+    fn get_or_try_init(
+        &self,
+        activate: impl FnOnce() -> Result<Ref<service!()>, ResolveError>,
+    ) -> Result<(Ref<service!()>, bool), ResolveError> {
+        let _lock = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(instance) = self.value.get() {
+            return Ok((instance.clone(), false));
+        }
+
+        let instance = activate()?;
+        let _ = self.value.set(instance.clone());
+        Ok((instance, true))
+    }
+}
+
+impl From<OnceLock<Ref<service!()>>> for Instance {
+    #[inline]
+    fn from(value: OnceLock<Ref<service!()>>) -> Self {
+        Self {
+            value,
+            lock: Mutex::default(),
+        }
     }
 }
 
@@ -21,30 +79,45 @@ pub struct ServiceDescriptor {
     service_type: Type,
     implementation_type: Type,
     dependencies: Vec<ServiceDependency>,
-    factory: Ref<ServiceFactory>,
-    instance: Ref<OnceLock<Ref<service!()>>>,
+    factory: Ref<Activate>,
+    instance: Ref<Instance>,
+    tracker: Option<Tracker>,
+    slot: Option<usize>,
 }
 
 impl ServiceDescriptor {
-    #[cfg(any(feature = "builder", feature = "inject"))]
     pub(crate) fn new(
         lifetime: ServiceLifetime,
         service_type: Type,
         implementation_type: Type,
         dependencies: Vec<ServiceDependency>,
         instance: OnceLock<Ref<service!()>>,
-        factory: Ref<ServiceFactory>,
+        factory: Ref<Activate>,
     ) -> Self {
         Self {
             lifetime,
             service_type,
             implementation_type,
             dependencies,
-            instance: Ref::new(instance),
+            instance: Ref::new(instance.into()),
             factory,
+            tracker: None,
+            slot: None,
         }
     }
 
+    #[cfg(any(feature = "builder", feature = "inject"))]
+    pub(crate) fn tracked_by(mut self, tracker: Option<Tracker>) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    // a scoped service owned by a service provider caches its instances in the
+    // scope at the slot assigned by the provider rather than in the descriptor
+    pub(crate) fn set_slot(&mut self, slot: usize) {
+        self.slot = Some(slot);
+    }
+
     /// Gets the [lifetime](ServiceLifetime) associated with the service descriptor.
     #[inline]
     pub fn lifetime(&self) -> ServiceLifetime {
@@ -74,12 +147,90 @@ impl ServiceDescriptor {
     /// # Arguments
     ///
     /// * `services` - The current [service provider](ServiceProvider)
+    ///
+    /// # Remarks
+    ///
+    /// If the service is [disposable](crate::Disposable), a newly created instance is tracked by the
+    /// [service provider](ServiceProvider) that owns it. Singletons are always owned by the root provider. If the
+    /// provider has an [observer](crate::ResolutionObserver), it is notified once the service has been resolved.
+    ///
+    /// # Panics
+    ///
+    /// The service or any of its dependencies cannot be activated.
+    #[inline]
     pub fn get(&self, services: &ServiceProvider) -> Ref<service!()> {
-        if self.lifetime == Transient {
-            return (self.factory)(services);
+        resolution::expect(self.try_get(services))
+    }
+
+    /// Attempts to get or create the service defined by the service descriptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The current [service provider](ServiceProvider)
+    ///
+    /// # Remarks
+    ///
+    /// If the service or any of its dependencies cannot be activated, the [error](ResolveError) is returned and a
+    /// cached instance is not created so a subsequent request activates the service again. Refer to [get](Self::get)
+    /// for more information.
+    pub fn try_get(&self, services: &ServiceProvider) -> Result<Ref<service!()>, ResolveError> {
+        let Some(observer) = services.observer() else {
+            return Ok(self.get_or_activate(services)?.0);
+        };
+
+        let parent = resolution::current_service();
+        let started = Instant::now();
+        let (instance, activated) = self.get_or_activate(services)?;
+
+        observer.on_resolved(&ResolutionEvent::new(self, !activated, started.elapsed(), parent));
+        Ok(instance)
+    }
+
+    fn get_or_activate(&self, services: &ServiceProvider) -> Result<(Ref<service!()>, bool), ResolveError> {
+        let instance = if self.lifetime == Transient {
+            None
+        } else {
+            Some(
+                self.slot
+                    .and_then(|slot| services.instance(slot))
+                    .unwrap_or(&self.instance),
+            )
+        };
+
+        if let Some(instance) = instance.and_then(Instance::get) {
+            return Ok((instance.clone(), false));
+        }
+
+        let _activation = Activation::enter(self.id(), &self.service_type)?;
+
+        if let Some(instance) = instance {
+            instance.get_or_try_init(|| self.activate(services))
+        } else {
+            Ok((self.activate(services)?, true))
+        }
+    }
+
+    // the factory is shared by every clone of a descriptor, which makes it a
+    // stable identity for the same registration across scopes
+    #[inline]
+    pub(crate) fn id(&self) -> usize {
+        Ref::as_ptr(&self.factory) as *const () as usize
+    }
+
+    fn activate(&self, services: &ServiceProvider) -> Result<Ref<service!()>, ResolveError> {
+        let singleton = (self.lifetime == Singleton && services.validates_scopes()).then_some(&self.service_type);
+        let instance = (self.factory)(&services.captured_by(singleton))?;
+
+        // a service can only be tracked as the type it is registered as
+        if let Some(track) = self.tracker {
+            let Some(item) = track(&instance) else {
+                return Err(ResolveError::type_mismatch(self.service_type.clone()));
+            };
+
+            services.track(self.lifetime, item);
         }
 
-        self.instance.get_or_init(|| (self.factory)(services)).clone()
+        Ok(instance)
     }
 
     pub(crate) fn clone_with(&self, dependencies: bool) -> Self {
@@ -90,14 +241,20 @@ impl ServiceDescriptor {
             dependencies: if dependencies {
                 self.dependencies.clone()
             } else {
-                Vec::new()
+                self.dependencies
+                    .iter()
+                    .filter(|dependency| dependency.activator().is_some())
+                    .cloned()
+                    .collect()
             },
             instance: if self.lifetime == Singleton {
                 self.instance.clone()
             } else {
-                Ref::new(OnceLock::new())
+                Ref::new(Instance::new())
             },
             factory: self.factory.clone(),
+            tracker: self.tracker,
+            slot: None,
         }
     }
 }
@@ -1,5 +1,6 @@
 /// Represents the possible service lifetimes.
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ServiceLifetime {
     /// Indicates that a single instance of the service will be created.
     Singleton,
@@ -0,0 +1,218 @@
+use crate::{Mut, Ref};
+use std::any::Any;
+use std::mem::take;
+use std::sync::{Mutex, PoisonError};
+
+/// Defines the behavior of a service that releases resources when its owning
+/// [ServiceProvider](crate::ServiceProvider) is disposed.
+pub trait Disposable {
+    /// Performs application-defined tasks associated with freeing, releasing, or resetting resources.
+    fn dispose(&self);
+}
+
+impl<T: Disposable + ?Sized> Disposable for Ref<T> {
+    #[inline]
+    fn dispose(&self) {
+        self.as_ref().dispose()
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        use std::error::Error;
+        use std::fmt::{Display, Formatter, Result as FormatResult};
+        use std::future::Future;
+        use std::pin::Pin;
+
+        /// Represents the future of an asynchronously disposed service.
+        pub type DisposeFuture<'a> = Pin<Box<dyn Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send + 'a>>;
+
+        /// Defines the behavior of a service that asynchronously releases resources when its owning
+        /// [ServiceProvider](crate::ServiceProvider) is disposed.
+        ///
+        /// # Remarks
+        ///
+        /// Asynchronous disposal only occurs when [dispose_async](crate::ServiceProvider::dispose_async) is awaited.
+        /// A service that is only asynchronously disposable is released, but not disposed, if its owning provider is
+        /// dropped or synchronously disposed instead.
+        pub trait AsyncDisposable: Send + Sync {
+            /// Asynchronously performs application-defined tasks associated with freeing, releasing, or resetting
+            /// resources.
+            fn dispose_async(&self) -> DisposeFuture<'_>;
+        }
+
+        impl<T: AsyncDisposable + ?Sized> AsyncDisposable for Ref<T> {
+            #[inline]
+            fn dispose_async(&self) -> DisposeFuture<'_> {
+                self.as_ref().dispose_async()
+            }
+        }
+
+        /// Represents the error that occurs when one or more services fail to be disposed.
+        #[derive(Debug)]
+        pub struct DisposeError {
+            errors: Vec<Box<dyn Error + Send + Sync>>,
+        }
+
+        impl DisposeError {
+            fn new(errors: Vec<Box<dyn Error + Send + Sync>>) -> Self {
+                Self { errors }
+            }
+
+            /// Gets the errors that occurred, in the order the services were disposed.
+            #[inline]
+            pub fn errors(&self) -> &[Box<dyn Error + Send + Sync>] {
+                &self.errors
+            }
+        }
+
+        impl Display for DisposeError {
+            fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+                if self.errors.len() == 1 {
+                    return write!(formatter, "{}", self.errors[0]);
+                }
+
+                write!(formatter, "One or more services failed to be disposed.")?;
+
+                for (i, error) in self.errors.iter().enumerate() {
+                    write!(formatter, "\n  [{}] {}", i + 1, error)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        impl Error for DisposeError {}
+
+        type Instance = Box<dyn Disposable + Send + Sync>;
+        pub(crate) type Tracker = fn(&Ref<dyn Any + Send + Sync>) -> Option<Tracked>;
+
+        impl<T: Disposable + ?Sized> Disposable for Mut<T> {
+            fn dispose(&self) {
+                if let Ok(service) = self.read() {
+                    service.dispose()
+                }
+            }
+        }
+
+        pub(crate) fn async_tracker<T: AsyncDisposable + ?Sized + Any>() -> Tracker {
+            |instance| Some(Tracked::Async(Box::new(instance.downcast_ref::<Ref<T>>()?.clone())))
+        }
+    } else {
+        type Instance = Box<dyn Disposable>;
+        pub(crate) type Tracker = fn(&Ref<dyn Any>) -> Option<Tracked>;
+
+        impl<T: Disposable + ?Sized> Disposable for Mut<T> {
+            fn dispose(&self) {
+                if let Ok(service) = self.try_borrow() {
+                    service.dispose()
+                }
+            }
+        }
+    }
+}
+
+/// Represents a disposable service instance tracked by a service provider.
+pub(crate) enum Tracked {
+    Sync(Instance),
+
+    #[cfg(feature = "async")]
+    Async(Box<dyn AsyncDisposable>),
+}
+
+macro_rules! tracker {
+    ($($traits:tt)+) => {
+        // the instance is always stored as Ref<Ref<T>>, which is the same shape the ServiceProvider uses to
+        // downcast during resolution. an instance of any other type cannot be tracked
+        pub(crate) fn tracker<T: Disposable + ?Sized + $($traits)+>() -> Tracker {
+            |instance| Some(Tracked::Sync(Box::new(instance.downcast_ref::<Ref<T>>()?.clone())))
+        }
+    };
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        tracker!(Any + Send + Sync);
+    } else {
+        tracker!(Any);
+    }
+}
+
+/// Represents the set of disposable service instances owned by a service provider.
+#[derive(Default)]
+pub(crate) struct Disposables {
+    items: Mutex<Vec<Tracked>>,
+}
+
+impl Disposables {
+    pub fn track(&self, item: Tracked) {
+        self.items.lock().unwrap_or_else(PoisonError::into_inner).push(item);
+    }
+
+    pub fn dispose(&self) {
+        for item in self.take().into_iter().rev() {
+            match item {
+                Tracked::Sync(instance) => instance.dispose(),
+                #[cfg(feature = "async")]
+                Tracked::Async(_) => {}
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub async fn dispose_async(&self) -> Result<(), DisposeError> {
+        let mut errors = Vec::new();
+
+        for item in self.take().into_iter().rev() {
+            match item {
+                Tracked::Sync(instance) => instance.dispose(),
+                Tracked::Async(instance) => {
+                    if let Err(error) = instance.dispose_async().await {
+                        errors.push(error);
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DisposeError::new(errors))
+        }
+    }
+
+    // release the lock before invoking any service so that a service
+    // that resolves another service during disposal cannot deadlock
+    fn take(&self) -> Vec<Tracked> {
+        take(&mut *self.items.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// Represents the owner of the disposable service instances of a scope.
+///
+/// # Remarks
+///
+/// The instances are disposed when the last service provider that owns the scope is dropped. A service provider
+/// supplied to a service factory never owns its scope so that a service which retains the provider cannot keep the
+/// scope that it belongs to alive. A scope also owns the root so that a singleton outlives every scope that resolved it.
+pub(crate) struct Owner {
+    disposables: Ref<Disposables>,
+    root: Option<Ref<Owner>>,
+}
+
+impl Owner {
+    pub fn new(disposables: Ref<Disposables>, root: Option<Ref<Owner>>) -> Self {
+        Self { disposables, root }
+    }
+
+    // the root owner is the only owner without a root of its own
+    pub fn root(owner: &Ref<Self>) -> Ref<Self> {
+        owner.root.clone().unwrap_or_else(|| owner.clone())
+    }
+}
+
+impl Drop for Owner {
+    fn drop(&mut self) {
+        self.disposables.dispose()
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    ServiceCollection, ServiceDependency, ServiceDescriptor, ServiceLifetime, ServiceProvider, Type, ValidationError,
+    ValidationResult,
+};
+use std::collections::{HashMap, HashSet};
+
+/// Represents the singletons that are activated when a provider is built.
+#[derive(Clone, Debug, Default)]
+pub(crate) enum EagerActivation {
+    #[default]
+    Disabled,
+    All,
+    Only(Vec<Type>),
+}
+
+type Lookup<'a> = HashMap<&'a Type, Vec<&'a ServiceDescriptor>>;
+
+/// Activates the selected singletons of the specified collection using the provider built from it.
+pub(crate) fn activate(
+    services: &ServiceCollection,
+    provider: &ServiceProvider,
+    activation: &EagerActivation,
+    parallel: bool,
+) -> Result<(), ValidationError> {
+    let targets: Vec<_> = match activation {
+        EagerActivation::Disabled => return Ok(()),
+        EagerActivation::All => services.iter().collect(),
+        EagerActivation::Only(types) => services
+            .iter()
+            .filter(|descriptor| types.contains(descriptor.service_type()))
+            .collect(),
+    };
+    let mut lookup = Lookup::with_capacity(services.len());
+
+    for descriptor in services {
+        lookup.entry(descriptor.service_type()).or_default().push(descriptor);
+    }
+
+    let mut results = Vec::new();
+
+    for level in levels(services, &lookup, targets) {
+        if parallel && level.len() > 1 {
+            activate_parallel(&level, provider, &mut results);
+        } else {
+            for descriptor in level {
+                if let Some(result) = activate_one(descriptor, provider) {
+                    results.push(result);
+                }
+            }
+        }
+    }
+
+    if results.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::fail(results))
+    }
+}
+
+fn activate_one(descriptor: &ServiceDescriptor, provider: &ServiceProvider) -> Option<ValidationResult> {
+    provider.activate_registration(descriptor).err().map(|error| {
+        ValidationResult::error(
+            "SingletonActivation",
+            descriptor.service_type().clone(),
+            format!(
+                "Singleton service '{}' could not be activated: {}",
+                descriptor.implementation_type().name(),
+                error
+            ),
+        )
+    })
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        // the singletons in the same level never depend on each other. a level is
+        // split into one chunk per available core so a large level does not spawn
+        // a thread for every singleton
+        fn activate_parallel(
+            level: &[&ServiceDescriptor],
+            provider: &ServiceProvider,
+            results: &mut Vec<ValidationResult>,
+        ) {
+            let workers = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+            let size = level.len().div_ceil(workers);
+
+            std::thread::scope(|scope| {
+                let activations: Vec<_> = level
+                    .chunks(size)
+                    .map(|chunk| {
+                        scope.spawn(move || {
+                            chunk
+                                .iter()
+                                .filter_map(|descriptor| activate_one(descriptor, provider))
+                                .collect::<Vec<_>>()
+                        })
+                    })
+                    .collect();
+
+                for activation in activations {
+                    results.extend(activation.join().unwrap());
+                }
+            });
+        }
+    } else {
+        fn activate_parallel(
+            level: &[&ServiceDescriptor],
+            provider: &ServiceProvider,
+            results: &mut Vec<ValidationResult>,
+        ) {
+            results.extend(level.iter().filter_map(|descriptor| activate_one(descriptor, provider)));
+        }
+    }
+}
+
+fn lookup<'a>(lookup: &'a Lookup<'a>, dependency: &ServiceDependency) -> &'a [&'a ServiceDescriptor] {
+    lookup
+        .get(dependency.injected_type())
+        .or_else(|| dependency.definition().and_then(|definition| lookup.get(definition)))
+        .map_or(&[], Vec::as_slice)
+}
+
+// singletons are grouped by the length of their longest dependency chain. a
+// singleton only depends on singletons in a lower level, so activating each
+// level in turn activates every singleton after all of its dependencies
+fn levels<'a>(
+    services: &'a ServiceCollection,
+    services_by_type: &'a Lookup<'a>,
+    targets: Vec<&'a ServiceDescriptor>,
+) -> Vec<Vec<&'a ServiceDescriptor>> {
+    let mut depths = HashMap::new();
+    let mut reachable = HashSet::new();
+    let mut queue = targets;
+
+    while let Some(descriptor) = queue.pop() {
+        if reachable.insert(key(descriptor)) {
+            for dependency in descriptor.dependencies() {
+                queue.extend(lookup(services_by_type, dependency));
+            }
+        }
+    }
+
+    let mut levels = Vec::<Vec<_>>::new();
+
+    // an open generic definition is only activated once it is closed
+    for descriptor in services.iter().filter(|descriptor| {
+        descriptor.lifetime() == ServiceLifetime::Singleton
+            && !descriptor.service_type().is_definition()
+            && reachable.contains(&key(descriptor))
+    }) {
+        let level = depth(descriptor, services_by_type, &mut depths);
+
+        if levels.len() <= level {
+            levels.resize_with(level + 1, Vec::new);
+        }
+
+        levels[level].push(descriptor);
+    }
+
+    levels.retain(|level| !level.is_empty());
+    levels
+}
+
+fn depth<'a>(
+    descriptor: &'a ServiceDescriptor,
+    services_by_type: &'a Lookup<'a>,
+    depths: &mut HashMap<usize, Option<usize>>,
+) -> usize {
+    match depths.get(&key(descriptor)) {
+        Some(Some(depth)) => return *depth,
+        Some(None) => return 0, // circular; reported by validation
+        None => {}
+    }
+
+    depths.insert(key(descriptor), None);
+
+    let depth = descriptor
+        .dependencies()
+        .iter()
+        .flat_map(|dependency| lookup(services_by_type, dependency))
+        .map(|next| depth(next, services_by_type, depths) + 1)
+        .max()
+        .unwrap_or(0);
+
+    depths.insert(key(descriptor), Some(depth));
+    depth
+}
+
+#[inline]
+fn key(descriptor: &ServiceDescriptor) -> usize {
+    descriptor as *const ServiceDescriptor as usize
+}
@@ -0,0 +1,432 @@
+use crate::resolution::expect;
+use crate::{KeyedRef, KeyedRefMut, Mut, Ref, RefMut, ResolveError, ServiceProvider};
+use std::any::Any;
+
+/// Represents a factory that resolves a service each time it is invoked.
+///
+/// # Remarks
+///
+/// Unlike [Lazy](crate::lazy::Lazy), a factory never caches the service it resolves. Each invocation of a factory for
+/// a transient service activates a new instance, while the instance of a singleton or scoped service is the same
+/// instance the [ServiceProvider] would otherwise resolve.
+///
+/// A factory with arguments is an [assisted](assisted) factory. Each invocation supplies the runtime arguments of the
+/// service while all of its other dependencies are resolved from the [ServiceProvider].
+pub struct Factory<T, A = ()> {
+    services: ServiceProvider,
+    create: fn(&ServiceProvider, A) -> Result<T, ResolveError>,
+}
+
+impl<T, A> Factory<T, A> {
+    fn new(services: ServiceProvider, create: fn(&ServiceProvider, A) -> Result<T, ResolveError>) -> Self {
+        Self { services, create }
+    }
+}
+
+impl<T> Factory<T> {
+    /// Resolves and returns the underlying service.
+    ///
+    /// # Panics
+    ///
+    /// The underlying service cannot be resolved.
+    #[inline]
+    pub fn create(&self) -> T {
+        expect(self.try_create())
+    }
+
+    /// Attempts to resolve and return the underlying service.
+    ///
+    /// # Remarks
+    ///
+    /// If the underlying service cannot be resolved, the [error](ResolveError) is returned instead of panicking.
+    #[inline]
+    pub fn try_create(&self) -> Result<T, ResolveError> {
+        (self.create)(&self.services, ())
+    }
+}
+
+macro_rules! create {
+    ($($arg:ident: $type:ident),+) => {
+        impl<T, $($type),+> Factory<T, ($($type,)+)> {
+            /// Resolves and returns the underlying service using the specified arguments.
+            ///
+            /// # Panics
+            ///
+            /// The underlying service cannot be resolved.
+            #[inline]
+            pub fn create(&self, $($arg: $type),+) -> T {
+                expect(self.try_create($($arg),+))
+            }
+
+            /// Attempts to resolve and return the underlying service using the specified arguments.
+            ///
+            /// # Remarks
+            ///
+            /// If the underlying service cannot be resolved, the [error](ResolveError) is returned instead of
+            /// panicking.
+            #[inline]
+            pub fn try_create(&self, $($arg: $type),+) -> Result<T, ResolveError> {
+                (self.create)(&self.services, ($($arg,)+))
+            }
+        }
+    };
+}
+
+create!(arg1: A1);
+create!(arg1: A1, arg2: A2);
+create!(arg1: A1, arg2: A2, arg3: A3);
+create!(arg1: A1, arg2: A2, arg3: A3, arg4: A4);
+create!(arg1: A1, arg2: A2, arg3: A3, arg4: A4, arg5: A5);
+create!(arg1: A1, arg2: A2, arg3: A3, arg4: A4, arg5: A5, arg6: A6);
+
+impl<T, A> Clone for Factory<T, A> {
+    fn clone(&self) -> Self {
+        Self::new(self.services.clone(), self.create)
+    }
+}
+
+#[inline]
+fn to_vec<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<Ref<T>>, ResolveError> {
+    services.try_get_all::<T>()
+}
+
+#[inline]
+fn to_vec_mut<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<RefMut<T>>, ResolveError> {
+    services.try_get_all_mut::<T>()
+}
+
+#[inline]
+fn to_keyed_vec<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRef<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, TSvc>()
+}
+
+#[inline]
+fn to_keyed_vec_mut<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRefMut<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, Mut<TSvc>>()
+}
+
+/// Creates and returns a factory for a required service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn exactly_one<T: Any + ?Sized>(services: ServiceProvider) -> Factory<Ref<T>> {
+    Factory::new(services, |sp, _| sp.try_get_required::<T>())
+}
+
+/// Creates and returns a factory for a required, mutable service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn exactly_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Factory<RefMut<T>> {
+    Factory::new(services, |sp, _| sp.try_get_required_mut::<T>())
+}
+
+/// Creates and returns a factory for a keyed, required service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn exactly_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Factory<KeyedRef<TKey, TSvc>> {
+    Factory::new(services, |sp, _| sp.try_get_required_by_key::<TKey, TSvc>())
+}
+
+/// Creates and returns a factory for a keyed, required, mutable service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn exactly_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<KeyedRefMut<TKey, TSvc>> {
+    Factory::new(services, |sp, _| sp.try_get_required_by_key_mut::<TKey, TSvc>())
+}
+
+/// Creates and returns a factory for an optional service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn zero_or_one<T: Any + ?Sized>(services: ServiceProvider) -> Factory<Option<Ref<T>>> {
+    Factory::new(services, |sp, _| sp.try_get::<T>())
+}
+
+/// Creates and returns a factory for an optional, mutable service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn zero_or_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Factory<Option<RefMut<T>>> {
+    Factory::new(services, |sp, _| sp.try_get::<Mut<T>>())
+}
+
+/// Creates and returns a factory for a keyed, optional service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn zero_or_one_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Option<KeyedRef<TKey, TSvc>>> {
+    Factory::new(services, |sp, _| sp.try_get_by_key::<TKey, TSvc>())
+}
+
+/// Creates and returns a factory for a keyed, optional, mutable service.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the service
+#[inline]
+pub fn zero_or_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Option<KeyedRefMut<TKey, TSvc>>> {
+    Factory::new(services, |sp, _| sp.try_get_by_key::<TKey, Mut<TSvc>>())
+}
+
+/// Creates and returns a factory for multiple services.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the services
+#[inline]
+pub fn zero_or_more<T: Any + ?Sized>(services: ServiceProvider) -> Factory<Vec<Ref<T>>> {
+    Factory::new(services, |sp, _| to_vec::<T>(sp))
+}
+
+/// Creates and returns a factory for multiple, mutable services.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the services
+#[inline]
+pub fn zero_or_more_mut<T: Any + ?Sized>(services: ServiceProvider) -> Factory<Vec<RefMut<T>>> {
+    Factory::new(services, |sp, _| to_vec_mut::<T>(sp))
+}
+
+/// Creates and returns a factory for multiple, keyed services.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the services
+#[inline]
+pub fn zero_or_more_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Vec<KeyedRef<TKey, TSvc>>> {
+    Factory::new(services, |sp, _| to_keyed_vec::<TKey, TSvc>(sp))
+}
+
+/// Creates and returns a factory for multiple, keyed, mutable services.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the services
+#[inline]
+pub fn zero_or_more_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Factory<Vec<KeyedRefMut<TKey, TSvc>>> {
+    Factory::new(services, |sp, _| to_keyed_vec_mut::<TKey, TSvc>(sp))
+}
+
+/// Creates and returns an assisted factory for a service that requires runtime arguments.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to resolve the dependencies of the service
+/// * `create` - The function used to create the service from the [ServiceProvider] and the runtime arguments
+///
+/// # Remarks
+///
+/// An assisted factory is usually registered by the `#[injectable]` attribute for a constructor that has one or more
+/// parameters decorated with `#[arg]`, but it can also be registered by hand. If the function returns an error,
+/// [create](Factory::create) panics with the error and [try_create](Factory::try_create) returns it.
+#[inline]
+pub fn assisted<T, A>(
+    services: ServiceProvider,
+    create: fn(&ServiceProvider, A) -> Result<T, ResolveError>,
+) -> Factory<T, A> {
+    Factory::new(services, create)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{factory, scoped_factory, singleton_as_self, transient_as_self, ServiceCollection, ValidationOptions};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static SEQUENCE: AtomicUsize = AtomicUsize::new(0);
+
+    struct Parser {
+        id: usize,
+    }
+
+    impl Default for Parser {
+        fn default() -> Self {
+            Self {
+                id: SEQUENCE.fetch_add(1, Ordering::Relaxed),
+            }
+        }
+    }
+
+    struct Consumer {
+        parsers: Factory<Ref<Parser>>,
+    }
+
+    impl Consumer {
+        fn new(parsers: Factory<Ref<Parser>>) -> Self {
+            Self { parsers }
+        }
+    }
+
+    #[test]
+    fn factory_should_return_new_transient_service_on_each_invocation() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(transient_as_self::<Parser>().from(|_| Ref::new(Parser::default())))
+            .add(
+                singleton_as_self::<Consumer>()
+                    .depends_on(crate::exactly_one::<Parser>())
+                    .from(|sp| Ref::new(Consumer::new(factory::exactly_one::<Parser>(sp.clone())))),
+            )
+            .build_provider()
+            .unwrap();
+        let consumer = provider.get_required::<Consumer>();
+
+        // act
+        let parser1 = consumer.parsers.create();
+        let parser2 = consumer.parsers.create();
+
+        // assert
+        assert_ne!(parser1.id, parser2.id);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn factory_should_return_same_scoped_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(scoped_factory(|_| Ref::new(Parser::default())))
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+        let factory = factory::exactly_one::<Parser>(scope.clone());
+
+        // act
+        let parser1 = factory.create();
+        let parser2 = factory.create();
+
+        // assert
+        assert!(Ref::ptr_eq(&parser1, &parser2));
+        assert!(Ref::ptr_eq(&parser1, &scope.get_required::<Parser>()));
+    }
+
+    #[test]
+    fn factory_should_return_optional_service() {
+        // arrange
+        let provider = ServiceCollection::new().build_provider().unwrap();
+        let factory = factory::zero_or_one::<Parser>(provider);
+
+        // act
+        let parser = factory.create();
+
+        // assert
+        assert!(parser.is_none());
+    }
+
+    #[test]
+    fn factory_should_return_all_services() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(transient_as_self::<Parser>().from(|_| Ref::new(Parser::default())))
+            .add(transient_as_self::<Parser>().from(|_| Ref::new(Parser::default())))
+            .build_provider()
+            .unwrap();
+        let factory = factory::zero_or_more::<Parser>(provider);
+
+        // act
+        let parsers = factory.create();
+
+        // assert
+        assert_eq!(parsers.len(), 2);
+    }
+
+    struct Order {
+        id: u64,
+        parser: Ref<Parser>,
+    }
+
+    #[test]
+    fn assisted_factory_should_create_service_with_arguments() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(singleton_as_self::<Parser>().from(|_| Ref::new(Parser::default())))
+            .add(transient_as_self::<Factory<Ref<Order>, (u64,)>>().from(|sp| {
+                Ref::new(factory::assisted(sp.clone(), |sp, (id,)| {
+                    Ok(Ref::new(Order {
+                        id,
+                        parser: sp.try_get_required::<Parser>()?,
+                    }))
+                }))
+            }))
+            .build_provider()
+            .unwrap();
+        let orders = provider.get_required::<Factory<Ref<Order>, (u64,)>>();
+
+        // act
+        let order1 = orders.create(1);
+        let order2 = orders.create(2);
+
+        // assert
+        assert_eq!(order1.id, 1);
+        assert_eq!(order2.id, 2);
+        assert!(Ref::ptr_eq(&order1.parser, &order2.parser));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be resolved for the singleton service")]
+    fn factory_of_singleton_should_not_resolve_scoped_service() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(scoped_factory(|_| Ref::new(Parser::default())))
+            .add(singleton_as_self::<Consumer>().from(|sp| Ref::new(Consumer::new(factory::exactly_one(sp.clone())))))
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
+            .unwrap();
+        let scope = provider.create_scope();
+        let consumer = scope.get_required::<Consumer>();
+
+        // act
+        let _ = consumer.parsers.create();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn try_create_should_return_error_when_service_cannot_be_resolved() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(scoped_factory(|_| Ref::new(Parser::default())))
+            .add(singleton_as_self::<Consumer>().from(|sp| Ref::new(Consumer::new(factory::exactly_one(sp.clone())))))
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
+            .unwrap();
+        let scope = provider.create_scope();
+        let consumer = scope.get_required::<Consumer>();
+
+        // act
+        let result = consumer.parsers.try_create();
+
+        // assert
+        assert!(matches!(result, Err(ResolveError::Captive { .. })));
+    }
+}
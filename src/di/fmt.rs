@@ -1,37 +1,104 @@
 mod context;
+mod explanation;
+mod graph;
 mod item;
+mod options;
 mod renderer;
 
+pub(crate) mod dot;
+pub(crate) mod mermaid;
+
+/// Contains a renderer that writes text colorized for a terminal.
 #[cfg(feature = "fmt")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fmt")))]
 pub mod terminal;
 
+/// Contains a renderer that writes plain text.
 pub mod text;
 
-pub use context::Context;
-pub use item::Item;
+pub use explanation::Explanation;
+pub use options::RenderOptions;
 pub use renderer::Renderer;
 
+use context::Context;
+use item::Item;
+
 use crate::{
+    validation,
     ServiceCardinality::{self, *},
-    ServiceCollection, ServiceDescriptor,
-    ServiceLifetime::*,
-    Type,
+    ServiceCollection, ServiceDependency, ServiceDescriptor,
+    ServiceLifetime::{self, *},
+    Type, ValidationOptions, ValidationSeverity,
 };
 use std::{
-    collections::HashMap,
-    fmt::{Formatter, Result},
+    collections::{HashMap, HashSet},
+    fmt::Result,
 };
 
-pub fn write(services: &ServiceCollection, mut renderer: impl Renderer, f: &mut Formatter<'_>) -> Result {
-    let count = services.len();
+pub(crate) fn write(services: &ServiceCollection, renderer: &mut impl Renderer, options: &RenderOptions) -> Result {
+    let roots: Vec<_> = services
+        .iter()
+        .filter(|descriptor| options.includes(descriptor.service_type()))
+        .collect();
 
-    if count == 0 {
-        return Ok(());
+    // dependencies are resolved against every service, not just the filtered ones
+    let lookup = index(services);
+    let mut context = Context::new(&lookup, options);
+
+    write_roots(&roots, &mut context, renderer)
+}
+
+pub(crate) fn explain(services: &ServiceCollection, service_type: &Type, renderer: &mut impl Renderer) -> Result {
+    let options = RenderOptions::new().effective_lifetimes(true);
+    let lookup = index(services);
+    let mut context = Context::new(&lookup, &options);
+
+    let Some(roots) = context.lookup(service_type) else {
+        renderer.branch('└')?;
+        renderer.write(' ')?;
+        append_service(service_type, ExactlyOne, renderer)?;
+        renderer.error("‼ Missing")?;
+        return renderer.write('\n');
+    };
+
+    write_roots(roots, &mut context, renderer)?;
+
+    let mut reachable = HashSet::new();
+    let mut queue = roots.clone();
+
+    while let Some(descriptor) = queue.pop() {
+        if reachable.insert(descriptor.service_type()) {
+            for dependency in descriptor.dependencies() {
+                queue.extend(context.resolve(dependency).into_iter().flatten());
+            }
+        }
     }
 
-    let last = count - 1;
-    let mut branches = Vec::<char>::new();
-    let mut lookup = HashMap::with_capacity(count);
+    let results = validation::evaluate(services, &ValidationOptions::default());
+    let mut first = true;
+
+    for result in results
+        .iter()
+        .filter(|result| reachable.contains(result.service_type()))
+    {
+        if first {
+            renderer.write('\n')?;
+            first = false;
+        }
+
+        match result.severity() {
+            ValidationSeverity::Error => renderer.error(&format!("‼ {}: {}", result.rule(), result.message()))?,
+            ValidationSeverity::Warning => renderer.warn(&format!("▲ {}: {}", result.rule(), result.message()))?,
+        }
+
+        renderer.write('\n')?;
+    }
+
+    Ok(())
+}
+
+fn index(services: &ServiceCollection) -> HashMap<&Type, Vec<&ServiceDescriptor>> {
+    let mut lookup = HashMap::with_capacity(services.len());
 
     for item in services {
         let key = item.service_type();
@@ -39,35 +106,34 @@ pub fn write(services: &ServiceCollection, mut renderer: impl Renderer, f: &mut
         descriptors.push(item);
     }
 
-    let mut context = Context::new(&lookup);
+    lookup
+}
 
-    branches.push('│');
-    branches.push(' ');
+fn write_roots<'a>(roots: &[&'a ServiceDescriptor], context: &mut Context<'a>, renderer: &mut impl Renderer) -> Result {
+    if roots.is_empty() {
+        return Ok(());
+    }
+
+    let last = roots.len() - 1;
+    let mut branches = vec!['│', ' '];
 
-    for (index, descriptor) in services.iter().enumerate() {
+    for (index, descriptor) in roots.iter().enumerate() {
         if index == last {
-            renderer.write('└', f)?;
+            renderer.branch('└')?;
             branches[0] = ' ';
         } else if index == 0 {
-            renderer.write('┌', f)?;
+            renderer.branch('┌')?;
         } else {
-            renderer.write('├', f)?;
+            renderer.branch('├')?;
         }
 
-        renderer.write(' ', f)?;
+        renderer.write(' ')?;
         context.reset(descriptor);
-        write_item(
-            Item::One(descriptor),
-            ExactlyOne,
-            &mut context,
-            0,
-            &mut branches,
-            f,
-            &mut renderer,
-        )?;
+        write_item(Item::One(descriptor), ExactlyOne, context, 0, &mut branches, renderer)?;
 
         if index != last {
-            renderer.write_str("│\n", f)?;
+            renderer.branch('│')?;
+            renderer.write('\n')?;
         }
     }
 
@@ -80,100 +146,121 @@ fn write_item(
     context: &mut Context,
     depth: usize,
     branches: &mut Vec<char>,
-    formatter: &mut Formatter,
     renderer: &mut impl Renderer,
 ) -> Result {
     match item {
         Item::One(sd) => {
-            append_service(sd.service_type(), cardinality, renderer, formatter)?;
+            append_service(sd.service_type(), cardinality, renderer)?;
 
             if context.is_invalid_lifetime(sd) {
-                renderer.error(
-                    format!("⧗ {} [{:?}]", sd.implementation_type().name(), sd.lifetime()),
-                    formatter,
-                )?;
+                renderer.error(&format!("⧗ {} [{:?}]", sd.implementation_type().name(), sd.lifetime()))?;
             } else {
-                append_implementation(sd, renderer, formatter)?;
+                append_implementation(sd, context.effective_lifetime(sd), renderer)?;
             }
         }
         Item::Many((ty, impl_count, _)) => {
-            append_service(ty, cardinality, renderer, formatter)?;
-            renderer.write_str(impl_count, formatter)?;
+            append_service(ty, cardinality, renderer)?;
+            renderer.write_str(impl_count)?;
         }
         Item::Warning((sd, msg)) => {
-            append_service(sd, cardinality, renderer, formatter)?;
-            renderer.warn(msg, formatter)?;
+            append_service(sd, cardinality, renderer)?;
+            renderer.warn(msg)?;
         }
         Item::Error((sd, msg)) => {
-            append_service(sd, cardinality, renderer, formatter)?;
-            renderer.error(msg, formatter)?;
+            append_service(sd, cardinality, renderer)?;
+            renderer.error(msg)?;
         }
     }
 
-    renderer.write('\n', formatter)?;
+    let has_children = match item {
+        Item::One(sd) => !rendered(sd).is_empty(),
+        Item::Many((_, _, children)) => !children.is_empty(),
+        _ => false,
+    };
+
+    if has_children && context.is_truncated(depth) {
+        renderer.write(' ')?;
+        renderer.info("…")?;
+        return renderer.write('\n');
+    }
+
+    if has_children && matches!(item, Item::One(sd) if context.is_collapsed(sd)) {
+        renderer.write(' ')?;
+        renderer.info("↑")?;
+        return renderer.write('\n');
+    }
+
+    renderer.write('\n')?;
 
     match item {
-        Item::One(child) => traverse_dependencies(child, context, depth, branches, formatter, renderer),
-        Item::Many((_, _, children)) => traverse_services(children, context, depth, branches, formatter, renderer),
+        Item::One(child) => traverse_dependencies(child, context, depth, branches, renderer),
+        Item::Many((_, _, children)) => traverse_services(children, context, depth, branches, renderer),
         _ => Ok(()),
     }
 }
 
-fn append_service(
-    ty: &Type,
-    cardinality: ServiceCardinality,
-    renderer: &mut impl Renderer,
-    f: &mut Formatter,
-) -> Result {
+fn append_service(ty: &Type, cardinality: ServiceCardinality, renderer: &mut impl Renderer) -> Result {
     let (type_, key) = Type::deconstruct(ty);
 
     if type_.starts_with("dyn") {
-        renderer.keyword("dyn", f)?;
-        renderer.write(' ', f)?;
-        renderer.service(&type_[(type_.char_indices().nth(4).unwrap().0)..], f)?;
+        renderer.keyword("dyn")?;
+        renderer.write(' ')?;
+        renderer.service(&type_[(type_.char_indices().nth(4).unwrap().0)..])?;
     } else {
-        renderer.implementation(type_, f)?;
+        renderer.implementation(type_)?;
     }
 
     if cardinality == ServiceCardinality::ZeroOrMore {
-        renderer.accent("*", f)?;
+        renderer.accent("*")?;
     } else if cardinality == ServiceCardinality::ZeroOrOne {
-        renderer.accent("?", f)?;
+        renderer.accent("?")?;
     }
 
     if let Some(name) = key {
-        renderer.write(' ', f)?;
-        renderer.info("[⚿ ", f)?;
-        renderer.info(name, f)?;
-        renderer.info("]", f)?;
+        renderer.write(' ')?;
+        renderer.info("[⚿ ")?;
+        renderer.info(name)?;
+        renderer.info("]")?;
     }
 
-    renderer.write_str(" → ", f)
+    renderer.write_str(" → ")
 }
 
-fn append_implementation(item: &ServiceDescriptor, renderer: &mut impl Renderer, f: &mut Formatter) -> Result {
-    renderer.implementation(item.implementation_type().name(), f)?;
-    renderer.write(' ', f)?;
+fn append_implementation(
+    item: &ServiceDescriptor,
+    effective: Option<ServiceLifetime>,
+    renderer: &mut impl Renderer,
+) -> Result {
+    renderer.implementation(item.implementation_type().name())?;
+    renderer.write(' ')?;
+
+    if let Some(effective) = effective {
+        return renderer.info(&format!("[{:?}, effectively {:?}]", item.lifetime(), effective));
+    }
 
     match item.lifetime() {
-        Scoped => renderer.info("[Scoped]", f),
-        Singleton => renderer.info("[Singleton]", f),
-        Transient => renderer.info("[Transient]", f),
+        Scoped => renderer.info("[Scoped]"),
+        Singleton => renderer.info("[Singleton]"),
+        Transient => renderer.info("[Transient]"),
     }
 }
 
-fn indent(branches: &mut Vec<char>, formatter: &mut Formatter, renderer: &mut impl Renderer, last: bool) -> Result {
-    for branch in &*branches {
-        renderer.write(*branch, formatter)?;
+fn indent(branches: &mut Vec<char>, renderer: &mut impl Renderer, last: bool) -> Result {
+    for &branch in &*branches {
+        if branch == ' ' {
+            renderer.write(branch)?;
+        } else {
+            renderer.branch(branch)?;
+        }
     }
 
     if last {
-        renderer.write('└', formatter)?;
+        renderer.branch('└')?;
     } else {
-        renderer.write('├', formatter)?;
+        renderer.branch('├')?;
     }
 
-    renderer.write(' ', formatter)?;
+    renderer.write(' ')?;
 
     if last {
         branches.push(' ');
@@ -190,22 +277,29 @@ fn unindent(branches: &mut Vec<char>) {
     branches.pop();
 }
 
+// the service provider is always available so it is never rendered as a dependency
+fn rendered(descriptor: &ServiceDescriptor) -> Vec<&ServiceDependency> {
+    descriptor.dependencies().iter().filter(|d| !d.is_provided()).collect()
+}
+
 fn traverse_dependencies(
     descriptor: &ServiceDescriptor,
     context: &mut Context,
     depth: usize,
     branches: &mut Vec<char>,
-    formatter: &mut Formatter,
     renderer: &mut impl Renderer,
 ) -> Result {
-    for (index, dependency) in descriptor.dependencies().iter().enumerate() {
+    let dependencies = rendered(descriptor);
+
+    for (index, dependency) in dependencies.iter().enumerate() {
         let type_ = dependency.injected_type();
         let cardinality = dependency.cardinality();
-        let last = index == descriptor.dependencies().len() - 1;
+        let last = index == dependencies.len() - 1;
 
-        indent(branches, formatter, renderer, last)?;
+        indent(branches, renderer, last)?;
+        context.enter_scope(dependency);
 
-        if let Some(children) = context.lookup(type_) {
+        if let Some(children) = context.resolve(dependency) {
             if cardinality == ZeroOrMore {
                 write_item(
                     Item::Many((type_, &format!("Count: {}", children.len()), children)),
@@ -213,7 +307,6 @@ fn traverse_dependencies(
                     context,
                     depth + 1,
                     branches,
-                    formatter,
                     renderer,
                 )?;
             } else {
@@ -227,7 +320,7 @@ fn traverse_dependencies(
                     };
 
                     context.enter(child);
-                    write_item(item, cardinality, context, depth + 1, branches, formatter, renderer)?;
+                    write_item(item, cardinality, context, depth + 1, branches, renderer)?;
                     context.exit();
                 }
             }
@@ -238,9 +331,10 @@ fn traverse_dependencies(
                 ZeroOrMore => Item::Warning((type_, "▲ Count: 0")),
             };
 
-            write_item(item, cardinality, context, depth + 1, branches, formatter, renderer)?;
+            write_item(item, cardinality, context, depth + 1, branches, renderer)?;
         }
 
+        context.exit_scope(dependency);
         unindent(branches);
     }
 
@@ -252,20 +346,18 @@ fn traverse_services(
     context: &mut Context,
     depth: usize,
     branches: &mut Vec<char>,
-    formatter: &mut Formatter,
     renderer: &mut impl Renderer,
 ) -> Result {
     for (index, descriptor) in descriptors.iter().enumerate() {
         let last = index == descriptors.len() - 1;
 
-        indent(branches, formatter, renderer, last)?;
+        indent(branches, renderer, last)?;
         write_item(
             Item::One(descriptor),
             ExactlyOne,
             context,
             depth + 1,
             branches,
-            formatter,
             renderer,
         )?;
         unindent(branches);
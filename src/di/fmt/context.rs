@@ -1,28 +1,33 @@
+use super::RenderOptions;
 use crate::{
-    ServiceDescriptor,
+    ServiceDependency, ServiceDescriptor,
     ServiceLifetime::{self, *},
     Type,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct Context<'a> {
-    scope: ServiceLifetime,
     visited: Vec<&'a ServiceDescriptor>,
+    boundaries: Vec<usize>,
     lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>,
+    options: &'a RenderOptions,
+    rendered: HashSet<usize>,
 }
 
 impl<'a> Context<'a> {
-    pub fn new(lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>) -> Self {
+    pub fn new(lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>, options: &'a RenderOptions) -> Self {
         Self {
-            scope: Transient,
             visited: Vec::new(),
+            boundaries: Vec::new(),
             lookup,
+            options,
+            rendered: HashSet::new(),
         }
     }
 
     pub fn reset(&mut self, descriptor: &'a ServiceDescriptor) {
-        self.scope = descriptor.lifetime();
         self.visited.clear();
+        self.boundaries.clear();
         self.visited.push(descriptor);
     }
 
@@ -30,26 +35,31 @@ impl<'a> Context<'a> {
         self.lookup.get(key)
     }
 
-    pub fn enter(&mut self, descriptor: &'a ServiceDescriptor) {
-        if self.scope != Singleton && descriptor.lifetime() == Singleton {
-            self.scope = Singleton;
-        }
+    // a closed generic dependency falls back to its open generic type definition
+    pub fn resolve(&self, dependency: &ServiceDependency) -> Option<&'a Vec<&'a ServiceDescriptor>> {
+        self.lookup(dependency.injected_type())
+            .or_else(|| dependency.definition().and_then(|definition| self.lookup(definition)))
+    }
 
+    pub fn enter(&mut self, descriptor: &'a ServiceDescriptor) {
         self.visited.push(descriptor);
     }
 
     pub fn exit(&mut self) {
         self.visited.pop();
+    }
 
-        for item in self.visited.iter().rev() {
-            self.scope = item.lifetime();
-
-            if self.scope == Singleton {
-                return;
-            }
+    // the services resolved through a scope boundary belong to their own scope
+    pub fn enter_scope(&mut self, dependency: &ServiceDependency) {
+        if dependency.is_scope_boundary() {
+            self.boundaries.push(self.visited.len());
         }
+    }
 
-        self.scope = self.visited.last().map_or(Transient, |s| s.lifetime());
+    pub fn exit_scope(&mut self, dependency: &ServiceDependency) {
+        if dependency.is_scope_boundary() {
+            self.boundaries.pop();
+        }
     }
 
     pub fn is_circular_ref(&self, descriptor: &ServiceDescriptor) -> bool {
@@ -62,7 +72,39 @@ impl<'a> Context<'a> {
         false
     }
 
+    // a scoped service is only captured by a singleton within the same scope
     pub fn is_invalid_lifetime(&self, descriptor: &ServiceDescriptor) -> bool {
-        self.scope == Singleton && descriptor.lifetime() == Scoped
+        let scope = &self.visited[self.boundaries.last().copied().unwrap_or_default()..];
+        descriptor.lifetime() == Scoped && scope.iter().any(|item| item.lifetime() == Singleton)
+    }
+
+    // a service lives at least as long as the longest-lived service in its path
+    pub fn effective_lifetime(&self, descriptor: &ServiceDescriptor) -> Option<ServiceLifetime> {
+        if !self.options.shows_effective_lifetimes() {
+            return None;
+        }
+
+        let effective = self
+            .visited
+            .iter()
+            .map(|item| item.lifetime())
+            .chain(std::iter::once(descriptor.lifetime()))
+            .max_by_key(|lifetime| match lifetime {
+                Transient => 0,
+                Scoped => 1,
+                Singleton => 2,
+            })?;
+
+        (effective != descriptor.lifetime()).then_some(effective)
+    }
+
+    pub fn is_truncated(&self, depth: usize) -> bool {
+        self.options.is_truncated(depth)
+    }
+
+    // the dependencies of a service are only rendered the first time it is
+    // rendered when repeated dependencies are collapsed
+    pub fn is_collapsed(&mut self, descriptor: &ServiceDescriptor) -> bool {
+        self.options.is_collapsed() && !self.rendered.insert(descriptor.id())
     }
 }
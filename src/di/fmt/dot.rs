@@ -0,0 +1,48 @@
+use super::graph::{Graph, ERROR, WARNING};
+use crate::ServiceCollection;
+use std::fmt::{Result, Write};
+
+const ESCAPES: &[(char, &str)] = &[('\\', "\\\\"), ('"', "\\\"")];
+
+pub fn write(services: &ServiceCollection, output: &mut impl Write) -> Result {
+    let graph = Graph::new(services);
+
+    output.write_str("digraph services {\n")?;
+    output.write_str("    node [shape=box, fontname=\"monospace\"];\n")?;
+    output.write_str("    edge [fontname=\"monospace\", fontsize=10];\n")?;
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        write!(
+            output,
+            "    {} [label=\"{}\"",
+            node.id(index),
+            node.label("\\n", ESCAPES)
+        )?;
+
+        if node.is_error() {
+            write!(output, ", style=dashed, color=\"{ERROR}\", fontcolor=\"{ERROR}\"")?;
+        } else if node.is_warning() {
+            write!(output, ", style=dashed, color=\"{WARNING}\", fontcolor=\"{WARNING}\"")?;
+        }
+
+        output.write_str("];\n")?;
+    }
+
+    for edge in &graph.edges {
+        write!(
+            output,
+            "    {} -> {} [label=\"{}\"",
+            graph.nodes[edge.from].id(edge.from),
+            graph.nodes[edge.to].id(edge.to),
+            edge.label()
+        )?;
+
+        if edge.is_error() {
+            write!(output, ", color=\"{ERROR}\", fontcolor=\"{ERROR}\"")?;
+        }
+
+        output.write_str("];\n")?;
+    }
+
+    output.write_str("}\n")
+}
@@ -0,0 +1,50 @@
+use super::Renderer;
+use crate::{ServiceCollection, Type};
+use std::fmt::{Display, Formatter, Result};
+
+/// Represents the explanation of how a single service in a [ServiceCollection] is resolved.
+///
+/// # Remarks
+///
+/// An explanation renders only the dependency tree that is reachable from the explained service. Each service is
+/// annotated with its effective [lifetime](crate::ServiceLifetime) and the tree is followed by any validation errors
+/// and warnings for the services it contains.
+pub struct Explanation<'a> {
+    services: &'a ServiceCollection,
+    service_type: Type,
+}
+
+impl<'a> Explanation<'a> {
+    pub(crate) fn new(services: &'a ServiceCollection, service_type: Type) -> Self {
+        Self { services, service_type }
+    }
+
+    /// Gets the [type](Type) of the explained service.
+    #[inline]
+    pub fn service_type(&self) -> &Type {
+        &self.service_type
+    }
+
+    /// Renders the explanation.
+    ///
+    /// # Arguments
+    ///
+    /// * `renderer` - The [renderer](Renderer) used to render the explanation
+    pub fn render(&self, renderer: &mut impl Renderer) -> Result {
+        super::explain(self.services, &self.service_type, renderer)
+    }
+}
+
+impl Display for Explanation<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        cfg_if::cfg_if! {
+            if #[cfg(feature = "fmt")] {
+                if f.alternate() {
+                    return self.render(&mut super::terminal::Renderer::new(f));
+                }
+            }
+        }
+
+        self.render(&mut super::text::Renderer::new(f))
+    }
+}
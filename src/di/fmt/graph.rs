@@ -0,0 +1,291 @@
+use crate::{
+    ServiceCardinality::{self, *},
+    ServiceCollection, ServiceDependency, ServiceDescriptor,
+    ServiceLifetime::*,
+    Type,
+};
+use std::collections::HashMap;
+
+pub const ERROR: &str = "#e74856";
+pub const WARNING: &str = "#c19c00";
+
+pub enum Node<'a> {
+    Service(&'a ServiceDescriptor),
+    Missing(&'a Type, ServiceCardinality),
+}
+
+impl Node<'_> {
+    pub fn id(&self, index: usize) -> String {
+        match self {
+            Self::Service(_) => format!("s{index}"),
+            Self::Missing(..) => format!("m{index}"),
+        }
+    }
+
+    // each line is escaped using the replacements of the output format
+    pub fn label(&self, separator: &str, escapes: &[(char, &str)]) -> String {
+        self.lines()
+            .iter()
+            .map(|line| escape(line, escapes))
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Missing(_, ExactlyOne))
+    }
+
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::Missing(_, ZeroOrOne | ZeroOrMore))
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        match self {
+            Self::Service(sd) => vec![
+                service(sd.service_type()),
+                String::from(sd.implementation_type().name()),
+                format!("[{:?}]", sd.lifetime()),
+            ],
+            Self::Missing(ty, cardinality) => vec![
+                service(ty),
+                String::from(match cardinality {
+                    ExactlyOne => "‼ Missing",
+                    ZeroOrOne => "▲ Missing",
+                    ZeroOrMore => "▲ Count: 0",
+                }),
+            ],
+        }
+    }
+}
+
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub cardinality: ServiceCardinality,
+    pub scope_boundary: bool,
+    pub circular: bool,
+    pub invalid_lifetime: bool,
+}
+
+impl Edge {
+    fn new(from: usize, to: usize, dependency: &ServiceDependency) -> Self {
+        Self {
+            from,
+            to,
+            cardinality: dependency.cardinality(),
+            scope_boundary: dependency.is_scope_boundary(),
+            circular: false,
+            invalid_lifetime: false,
+        }
+    }
+
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.circular || self.invalid_lifetime
+    }
+
+    pub fn label(&self) -> String {
+        let mut label = format!("{:?}", self.cardinality);
+
+        if self.circular {
+            label.push_str(" ♺");
+        }
+
+        if self.invalid_lifetime {
+            label.push_str(" ⧗");
+        }
+
+        label
+    }
+}
+
+// unlike the tree, which repeats a service wherever it is a dependency, a graph
+// has exactly one node per registration and one node per missing service
+pub struct Graph<'a> {
+    pub nodes: Vec<Node<'a>>,
+    pub edges: Vec<Edge>,
+}
+
+impl<'a> Graph<'a> {
+    pub fn new(services: &'a ServiceCollection) -> Self {
+        let mut nodes: Vec<_> = services.iter().map(Node::Service).collect();
+        let mut lookup = HashMap::<&Type, Vec<usize>>::with_capacity(nodes.len());
+        let mut missing = HashMap::<&Type, usize>::new();
+        let mut edges = Vec::new();
+
+        for (index, descriptor) in services.iter().enumerate() {
+            lookup.entry(descriptor.service_type()).or_default().push(index);
+        }
+
+        for (from, descriptor) in services.iter().enumerate() {
+            for dependency in descriptor.dependencies().iter().filter(|d| !d.is_provided()) {
+                let type_ = dependency.injected_type();
+                let cardinality = dependency.cardinality();
+                let targets = lookup
+                    .get(type_)
+                    .or_else(|| dependency.definition().and_then(|definition| lookup.get(definition)));
+
+                if let Some(targets) = targets {
+                    edges.extend(targets.iter().map(|&to| Edge::new(from, to, dependency)));
+                } else {
+                    let to = *missing.entry(type_).or_insert_with(|| {
+                        nodes.push(Node::Missing(type_, cardinality));
+                        nodes.len() - 1
+                    });
+
+                    // a required dependency takes precedence over an optional one
+                    if cardinality == ExactlyOne {
+                        nodes[to] = Node::Missing(type_, cardinality);
+                    }
+
+                    edges.push(Edge::new(from, to, dependency));
+                }
+            }
+        }
+
+        let mut graph = Self { nodes, edges };
+
+        graph.flag_circular();
+        graph.flag_invalid_lifetimes();
+        graph
+    }
+
+    fn successors(&self, include: impl Fn(&Edge) -> bool) -> Vec<Vec<usize>> {
+        let mut successors = vec![Vec::new(); self.nodes.len()];
+
+        for edge in self.edges.iter().filter(|edge| include(edge)) {
+            successors[edge.from].push(edge.to);
+        }
+
+        successors
+    }
+
+    // an edge is part of a cycle when its source and target are in the same strongly connected component
+    fn flag_circular(&mut self) {
+        let successors = self.successors(|_| true);
+        let mut components = Components::new(&successors);
+
+        for node in 0..self.nodes.len() {
+            if components.index[node].is_none() {
+                components.visit(node);
+            }
+        }
+
+        for edge in &mut self.edges {
+            edge.circular = components.component[edge.from] == components.component[edge.to];
+        }
+    }
+
+    // a scoped service is invalid wherever it is reachable from a singleton, unless it is
+    // resolved behind a scope boundary, which resolves its dependencies within its own scope
+    fn flag_invalid_lifetimes(&mut self) {
+        let successors = self.successors(|edge| !edge.scope_boundary);
+        let mut captive = vec![false; self.nodes.len()];
+        let mut stack: Vec<_> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, node)| match node {
+                Node::Service(sd) if sd.lifetime() == Singleton => Some(index),
+                _ => None,
+            })
+            .collect();
+
+        while let Some(node) = stack.pop() {
+            if !captive[node] {
+                captive[node] = true;
+                stack.extend(&successors[node]);
+            }
+        }
+
+        for edge in &mut self.edges {
+            edge.invalid_lifetime = captive[edge.from]
+                && !edge.scope_boundary
+                && matches!(self.nodes[edge.to], Node::Service(sd) if sd.lifetime() == Scoped);
+        }
+    }
+}
+
+// finds the strongly connected components of a graph in a single pass using tarjan's algorithm
+struct Components<'a> {
+    successors: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    component: Vec<usize>,
+    next: usize,
+    count: usize,
+}
+
+impl<'a> Components<'a> {
+    fn new(successors: &'a [Vec<usize>]) -> Self {
+        let len = successors.len();
+
+        Self {
+            successors,
+            index: vec![None; len],
+            low: vec![0; len],
+            stack: Vec::new(),
+            on_stack: vec![false; len],
+            component: vec![0; len],
+            next: 0,
+            count: 0,
+        }
+    }
+
+    fn visit(&mut self, node: usize) {
+        let successors = self.successors;
+
+        self.index[node] = Some(self.next);
+        self.low[node] = self.next;
+        self.next += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &next in &successors[node] {
+            match self.index[next] {
+                None => {
+                    self.visit(next);
+                    self.low[node] = self.low[node].min(self.low[next]);
+                }
+                Some(index) if self.on_stack[next] => self.low[node] = self.low[node].min(index),
+                _ => {}
+            }
+        }
+
+        // the node is the root of a component, which contains every node above it on the stack
+        if self.index[node] == Some(self.low[node]) {
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                self.component[member] = self.count;
+
+                if member == node {
+                    break;
+                }
+            }
+
+            self.count += 1;
+        }
+    }
+}
+
+pub fn escape(text: &str, escapes: &[(char, &str)]) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match escapes.iter().find(|(target, _)| *target == ch) {
+            Some((_, replacement)) => escaped.push_str(replacement),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+fn service(ty: &Type) -> String {
+    match Type::deconstruct(ty) {
+        (name, Some(key)) => format!("{name} [⚿ {key}]"),
+        (name, None) => String::from(name),
+    }
+}
@@ -0,0 +1,66 @@
+use super::graph::{Graph, Node, ERROR, WARNING};
+use crate::ServiceCollection;
+use std::fmt::{Result, Write};
+
+const ESCAPES: &[(char, &str)] = &[('"', "#quot;"), ('<', "#lt;"), ('>', "#gt;")];
+
+pub fn write(services: &ServiceCollection, output: &mut impl Write) -> Result {
+    let graph = Graph::new(services);
+
+    output.write_str("flowchart LR\n")?;
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        write!(output, "    {}[\"{}\"]", node.id(index), node.label("<br/>", ESCAPES))?;
+
+        if node.is_error() {
+            output.write_str(":::error")?;
+        } else if node.is_warning() {
+            output.write_str(":::warning")?;
+        }
+
+        output.write_char('\n')?;
+    }
+
+    for edge in &graph.edges {
+        writeln!(
+            output,
+            "    {} -->|\"{}\"| {}",
+            graph.nodes[edge.from].id(edge.from),
+            edge.label(),
+            graph.nodes[edge.to].id(edge.to)
+        )?;
+    }
+
+    // mermaid styles an edge by the order in which it is declared
+    let errors: Vec<_> = graph
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| edge.is_error())
+        .map(|(index, _)| index.to_string())
+        .collect();
+
+    if !errors.is_empty() {
+        writeln!(
+            output,
+            "    linkStyle {} stroke:{ERROR},color:{ERROR}",
+            errors.join(",")
+        )?;
+    }
+
+    if graph.nodes.iter().any(Node::is_error) {
+        writeln!(
+            output,
+            "    classDef error stroke:{ERROR},color:{ERROR},stroke-dasharray:4"
+        )?;
+    }
+
+    if graph.nodes.iter().any(Node::is_warning) {
+        writeln!(
+            output,
+            "    classDef warning stroke:{WARNING},color:{WARNING},stroke-dasharray:4"
+        )?;
+    }
+
+    Ok(())
+}
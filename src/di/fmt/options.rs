@@ -0,0 +1,94 @@
+use crate::Type;
+
+type Filter = dyn Fn(&Type) -> bool;
+
+/// Represents the options used to render a [ServiceCollection](crate::ServiceCollection).
+#[derive(Default)]
+pub struct RenderOptions {
+    max_depth: Option<usize>,
+    collapse: bool,
+    effective_lifetimes: bool,
+    filter: Option<Box<Filter>>,
+}
+
+impl RenderOptions {
+    /// Initializes new render options, which render every service and all of its dependencies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum depth of the rendered dependencies.
+    ///
+    /// # Arguments
+    ///
+    /// * `depth` - The maximum depth, where zero only renders the registered services
+    ///
+    /// # Remarks
+    ///
+    /// A service whose dependencies exceed the maximum depth is marked with `…`.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Sets a value indicating whether repeated dependencies are collapsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True to only render the dependencies of a service the first time it is rendered
+    ///
+    /// # Remarks
+    ///
+    /// A service whose dependencies have been collapsed is marked with `↑`.
+    pub fn collapse(mut self, value: bool) -> Self {
+        self.collapse = value;
+        self
+    }
+
+    /// Sets a value indicating whether the effective lifetime of each service is rendered.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True to render the effective lifetime of each service
+    ///
+    /// # Remarks
+    ///
+    /// A service lives at least as long as the longest-lived service that depends on it. For example, a transient
+    /// service that is a dependency of a singleton service is effectively a singleton. The effective lifetime is only
+    /// rendered when it differs from the registered lifetime.
+    pub fn effective_lifetimes(mut self, value: bool) -> Self {
+        self.effective_lifetimes = value;
+        self
+    }
+
+    /// Sets the filter used to select the registered services to render.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The function that returns true if the specified service [type](Type) is rendered
+    ///
+    /// # Remarks
+    ///
+    /// The filter only applies to the registered services at the root of the tree. The dependencies of a rendered
+    /// service are always rendered.
+    pub fn filter<F: Fn(&Type) -> bool + 'static>(mut self, predicate: F) -> Self {
+        self.filter = Some(Box::new(predicate));
+        self
+    }
+
+    pub(crate) fn is_truncated(&self, depth: usize) -> bool {
+        self.max_depth.is_some_and(|max| depth >= max)
+    }
+
+    pub(crate) fn is_collapsed(&self) -> bool {
+        self.collapse
+    }
+
+    pub(crate) fn shows_effective_lifetimes(&self) -> bool {
+        self.effective_lifetimes
+    }
+
+    pub(crate) fn includes(&self, service_type: &Type) -> bool {
+        self.filter.as_ref().is_none_or(|filter| filter(service_type))
+    }
+}
@@ -1,21 +1,98 @@
-use std::fmt::{Formatter, Result};
+use std::fmt::Result;
 
+/// Defines the behavior of a renderer for the services in a [ServiceCollection](crate::ServiceCollection).
+///
+/// # Remarks
+///
+/// A renderer receives the tree of services as a sequence of text fragments. Each fragment is classified so that a
+/// renderer can decorate it; for example, with colors or markup. Only [write_str](Renderer::write_str) must be
+/// implemented. By default, every other function writes its text unchanged.
 pub trait Renderer {
-    fn write(&mut self, ch: char, f: &mut Formatter<'_>) -> Result;
+    /// Writes unclassified text.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn write_str(&mut self, text: &str) -> Result;
 
-    fn write_str<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes an unclassified character.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The character to write
+    fn write(&mut self, ch: char) -> Result {
+        self.write_str(ch.encode_utf8(&mut [0; 4]))
+    }
 
-    fn service<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes a branch of the tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `ch` - The box-drawing character for the branch, which is one of `┌`, `├`, `└`, or `│`
+    fn branch(&mut self, ch: char) -> Result {
+        self.write(ch)
+    }
 
-    fn implementation<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes the name of a service type.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn service(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 
-    fn keyword<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes the name of an implementation type.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn implementation(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 
-    fn info<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes a keyword, such as `dyn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn keyword(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 
-    fn warn<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes supplemental information, such as a key or lifetime.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn info(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 
-    fn error<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes a warning.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn warn(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 
-    fn accent<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result;
+    /// Writes an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn error(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
+
+    /// Writes an accent, such as the cardinality of a dependency.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to write
+    fn accent(&mut self, text: &str) -> Result {
+        self.write_str(text)
+    }
 }
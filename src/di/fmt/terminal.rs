@@ -1,42 +1,61 @@
 use colored::Colorize;
-use std::fmt::{Display, Formatter, Result, Write};
+use std::fmt::{Result, Write};
 
-pub struct Renderer;
+/// Represents a [renderer](super::Renderer) that writes text colorized for a terminal.
+pub struct Renderer<W> {
+    output: W,
+}
+
+impl<W: Write> Renderer<W> {
+    /// Initializes a new terminal renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output to write to
+    pub fn new(output: W) -> Self {
+        Self { output }
+    }
+
+    /// Consumes the renderer and returns its output.
+    pub fn into_inner(self) -> W {
+        self.output
+    }
+}
 
-impl super::Renderer for Renderer {
-    fn write(&mut self, ch: char, f: &mut Formatter<'_>) -> Result {
-        f.write_char(ch)
+impl<W: Write> super::Renderer for Renderer<W> {
+    fn write_str(&mut self, text: &str) -> Result {
+        self.output.write_str(text)
     }
 
-    fn write_str<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
+    fn write(&mut self, ch: char) -> Result {
+        self.output.write_char(ch)
     }
 
-    fn keyword<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(75, 154, 214).fmt(f)
+    fn keyword(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(75, 154, 214))
     }
 
-    fn service<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(158, 211, 163).fmt(f)
+    fn service(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(158, 211, 163))
     }
 
-    fn implementation<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(78, 201, 176).fmt(f)
+    fn implementation(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(78, 201, 176))
     }
 
-    fn info<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(118, 118, 118).fmt(f)
+    fn info(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(118, 118, 118))
     }
 
-    fn warn<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(220, 220, 170).fmt(f)
+    fn warn(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(220, 220, 170))
     }
 
-    fn error<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(231, 72, 86).fmt(f)
+    fn error(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(231, 72, 86))
     }
 
-    fn accent<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        text.as_ref().truecolor(218, 112, 179).fmt(f)
+    fn accent(&mut self, text: &str) -> Result {
+        write!(self.output, "{}", text.truecolor(218, 112, 179))
     }
 }
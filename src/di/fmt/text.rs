@@ -1,41 +1,32 @@
-use std::fmt::{Formatter, Result, Write};
+use std::fmt::{Result, Write};
 
-pub struct Renderer;
-
-impl super::Renderer for Renderer {
-    fn write(&mut self, ch: char, f: &mut Formatter<'_>) -> Result {
-        f.write_char(ch)
-    }
-
-    fn write_str<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
-    }
-
-    fn service<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
-    }
-
-    fn implementation<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
-    }
-
-    fn keyword<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
-    }
+/// Represents a [renderer](super::Renderer) that writes plain text.
+pub struct Renderer<W> {
+    output: W,
+}
 
-    fn info<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
+impl<W: Write> Renderer<W> {
+    /// Initializes a new text renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `output` - The output to write to
+    pub fn new(output: W) -> Self {
+        Self { output }
     }
 
-    fn warn<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
+    /// Consumes the renderer and returns its output.
+    pub fn into_inner(self) -> W {
+        self.output
     }
+}
 
-    fn error<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
+impl<W: Write> super::Renderer for Renderer<W> {
+    fn write_str(&mut self, text: &str) -> Result {
+        self.output.write_str(text)
     }
 
-    fn accent<T: AsRef<str>>(&mut self, text: T, f: &mut Formatter<'_>) -> Result {
-        f.write_str(text.as_ref())
+    fn write(&mut self, ch: char) -> Result {
+        self.output.write_char(ch)
     }
 }
@@ -0,0 +1,61 @@
+use crate::{Ref, ServiceDependency, ServiceProvider};
+
+/// Defines the behavior of an open generic service.
+///
+/// # Remarks
+///
+/// An open generic service is registered once for its generic type definition and activated for any closed type
+/// that is requested. Every closed type identifies the same generic type definition, which is the type closed with
+/// `()` for each type argument by convention. Rust monomorphizes generic code at compile time so the activation of a closed type is
+/// provided by a generic implementation of this trait, which is invoked the first time the closed type is requested
+/// with [get_generic](ServiceProvider::get_generic) or one of its variants. A closed type that is declared as the
+/// dependency of another service, such as with `exactly_one_generic`, can also be requested like any other service.
+///
+/// # Example
+///
+/// ```
+/// use di::{scoped_generic, OpenGeneric, Ref, ServiceCollection, ServiceProvider};
+/// use std::marker::PhantomData;
+///
+/// struct Repository<T>(PhantomData<T>);
+///
+/// impl<T> OpenGeneric for Repository<T> {
+///     type Definition = Repository<()>;
+///
+///     fn activate(_services: &ServiceProvider) -> Ref<Self> {
+///         Ref::new(Repository(PhantomData))
+///     }
+/// }
+///
+/// struct User;
+/// struct Order;
+///
+/// let provider = ServiceCollection::new()
+///     .add(scoped_generic::<Repository<()>>())
+///     .build_provider()
+///     .unwrap();
+///
+/// let users = provider.get_required_generic::<Repository<User>>();
+/// let orders = provider.get_required_generic::<Repository<Order>>();
+/// ```
+pub trait OpenGeneric {
+    /// The generic type definition shared by every closed type of the service.
+    type Definition: ?Sized;
+
+    /// Gets the [dependencies](ServiceDependency) declared by the open generic service.
+    ///
+    /// # Remarks
+    ///
+    /// The dependencies are declared when the generic type definition is registered and apply to every closed type
+    /// of the service, so they cannot depend on its type arguments. By default, there are no dependencies.
+    fn dependencies() -> Vec<ServiceDependency> {
+        Vec::new()
+    }
+
+    /// Activates a new instance of the closed generic service.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [ServiceProvider] used to resolve dependencies
+    fn activate(services: &ServiceProvider) -> Ref<Self>;
+}
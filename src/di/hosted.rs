@@ -0,0 +1,259 @@
+use crate::{ServiceCollection, ServiceDescriptor, Type};
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+
+macro_rules! hosted {
+    ($($bounds:tt)*) => {
+        /// Defines the behavior of a service whose lifecycle is managed by a [ServiceProvider](crate::ServiceProvider).
+        ///
+        /// # Remarks
+        ///
+        /// A hosted service is registered like any other service using `dyn HostedService` as the service type. A
+        /// hosted service is typically a singleton so that the instance that is stopped is the same instance that was
+        /// started.
+        ///
+        /// # Example
+        ///
+        /// ```
+        /// use di::*;
+        /// use std::error::Error;
+        ///
+        /// struct Worker;
+        ///
+        /// impl HostedService for Worker {
+        ///     fn start(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ///         Ok(())
+        ///     }
+        ///
+        ///     fn stop(&self) -> Result<(), Box<dyn Error + Send + Sync>> {
+        ///         Ok(())
+        ///     }
+        /// }
+        ///
+        /// let provider = ServiceCollection::new()
+        ///     .add(singleton::<dyn HostedService, Worker>().from(|_| Ref::new(Worker)))
+        ///     .build_provider()
+        ///     .unwrap();
+        ///
+        /// provider.start_all().unwrap();
+        /// provider.stop_all().unwrap();
+        /// ```
+        pub trait HostedService $($bounds)* {
+            /// Starts the service.
+            fn start(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+
+            /// Stops the service.
+            fn stop(&self) -> Result<(), Box<dyn Error + Send + Sync>>;
+        }
+    };
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        use std::future::Future;
+        use std::pin::Pin;
+
+        hosted!(: Send + Sync);
+
+        /// Represents the future of an asynchronously started or stopped service.
+        pub type HostFuture<'a> = Pin<Box<dyn Future<Output = Result<(), Box<dyn Error + Send + Sync>>> + Send + 'a>>;
+
+        /// Defines the behavior of a service whose lifecycle is asynchronously managed by a
+        /// [ServiceProvider](crate::ServiceProvider).
+        ///
+        /// # Remarks
+        ///
+        /// An asynchronously hosted service is only started when
+        /// [start_all_async](crate::ServiceProvider::start_all_async) is awaited and only stopped when
+        /// [stop_all_async](crate::ServiceProvider::stop_all_async) is awaited.
+        pub trait AsyncHostedService: Send + Sync {
+            /// Asynchronously starts the service.
+            fn start_async(&self) -> HostFuture<'_>;
+
+            /// Asynchronously stops the service.
+            fn stop_async(&self) -> HostFuture<'_>;
+        }
+    } else {
+        hosted!();
+    }
+}
+
+/// Represents the error that occurs when one or more hosted services fail to be started or stopped.
+#[derive(Debug)]
+pub struct HostError {
+    stopping: bool,
+    errors: Vec<Box<dyn Error + Send + Sync>>,
+}
+
+impl HostError {
+    /// Gets the errors that occurred, in the order the services were started or stopped.
+    #[inline]
+    pub fn errors(&self) -> &[Box<dyn Error + Send + Sync>] {
+        &self.errors
+    }
+}
+
+impl Display for HostError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        if self.errors.len() == 1 {
+            return write!(formatter, "{}", self.errors[0]);
+        }
+
+        write!(
+            formatter,
+            "One or more hosted services failed to {}.",
+            if self.stopping { "stop" } else { "start" }
+        )?;
+
+        for (i, error) in self.errors.iter().enumerate() {
+            write!(formatter, "\n  [{}] {}", i + 1, error)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for HostError {}
+
+/// Completes starting or stopping hosted services with the specified errors, if any.
+pub(crate) fn complete(stopping: bool, errors: Vec<Box<dyn Error + Send + Sync>>) -> Result<(), HostError> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(HostError { stopping, errors })
+    }
+}
+
+/// Orders the specified hosted services so that every service follows the hosted services it depends on.
+///
+/// # Remarks
+///
+/// A hosted service depends on another hosted service when the registration of the other service, or a registration
+/// of its implementation as itself, is reachable from its declared dependencies. Services are grouped by the length of
+/// their longest chain of hosted dependencies, which places every service after all of its dependencies. Services in
+/// the same group retain their registration order.
+pub(crate) fn order<'a, T, I>(items: Vec<(T, &'a ServiceDescriptor)>, find: impl Fn(&Type) -> I) -> Vec<T>
+where
+    I: Iterator<Item = &'a ServiceDescriptor>,
+{
+    // items are in the order they are found for their service type
+    let keys: Vec<_> = items
+        .iter()
+        .enumerate()
+        .map(|(i, (_, descriptor))| {
+            let service_type = descriptor.service_type();
+            let index = items[..i]
+                .iter()
+                .filter(|(_, other)| other.service_type() == service_type)
+                .count();
+            (service_type, index)
+        })
+        .collect();
+    let reachable: Vec<_> = items
+        .iter()
+        .map(|(_, descriptor)| reachable(descriptor, &find))
+        .collect();
+    let dependencies: Vec<Vec<_>> = reachable
+        .iter()
+        .enumerate()
+        .map(|(i, (types, registrations))| {
+            (0..items.len())
+                .filter(|&j| {
+                    i != j && (registrations.contains(&keys[j]) || types.contains(items[j].1.implementation_type()))
+                })
+                .collect()
+        })
+        .collect();
+    let mut depths = vec![None; items.len()];
+    let mut order: Vec<_> = (0..items.len())
+        .map(|i| (depth(i, &dependencies, &mut depths), i))
+        .collect();
+
+    order.sort_unstable();
+
+    let mut items: Vec<_> = items.into_iter().map(|(item, _)| Some(item)).collect();
+    order.into_iter().map(|(_, i)| items[i].take().unwrap()).collect()
+}
+
+fn depth(index: usize, dependencies: &[Vec<usize>], depths: &mut [Option<Option<usize>>]) -> usize {
+    match depths[index] {
+        Some(Some(depth)) => return depth,
+        Some(None) => return 0, // circular; reported by validation
+        None => {}
+    }
+
+    depths[index] = Some(None);
+
+    let depth = dependencies[index]
+        .iter()
+        .map(|&next| depth(next, dependencies, depths) + 1)
+        .max()
+        .unwrap_or(0);
+
+    depths[index] = Some(Some(depth));
+    depth
+}
+
+// a registration is identified by its service type and the index at which it is
+// found for that type. the implementation type is not unique to a registration,
+// such as for every factory registration of the same service type
+type Registration<'a> = (&'a Type, usize);
+
+fn reachable<'a, I>(
+    descriptor: &'a ServiceDescriptor,
+    find: &impl Fn(&Type) -> I,
+) -> (HashSet<&'a Type>, HashSet<Registration<'a>>)
+where
+    I: Iterator<Item = &'a ServiceDescriptor>,
+{
+    let mut types = HashSet::new();
+    let mut registrations = HashSet::new();
+    let mut stack: Vec<_> = descriptor.dependencies().iter().collect();
+
+    while let Some(dependency) = stack.pop() {
+        if types.insert(dependency.injected_type()) {
+            for (index, next) in find(dependency.injected_type()).enumerate() {
+                registrations.insert((next.service_type(), index));
+                stack.extend(next.dependencies());
+            }
+        }
+    }
+
+    (types, registrations)
+}
+
+/// Gets the service types whose dependencies must be retained by a provider in order to start the hosted services
+/// of the specified collection in dependency order.
+pub(crate) fn retained(services: &ServiceCollection) -> HashSet<&Type> {
+    let mut lookup = HashMap::<&Type, Vec<&ServiceDescriptor>>::new();
+
+    for descriptor in services {
+        lookup.entry(descriptor.service_type()).or_default().push(descriptor);
+    }
+
+    let mut types = HashSet::new();
+    #[cfg(feature = "async")]
+    let roots = [Type::of::<dyn HostedService>(), Type::of::<dyn AsyncHostedService>()];
+    #[cfg(not(feature = "async"))]
+    let roots = [Type::of::<dyn HostedService>()];
+    let mut stack: Vec<_> = roots
+        .iter()
+        .filter_map(|key| lookup.get_key_value(key).map(|(key, _)| *key))
+        .collect();
+
+    while let Some(key) = stack.pop() {
+        if types.insert(key) {
+            for descriptor in lookup.get(key).into_iter().flatten() {
+                stack.extend(
+                    descriptor
+                        .dependencies()
+                        .iter()
+                        .map(|dependency| dependency.injected_type()),
+                );
+            }
+        }
+    }
+
+    types
+}
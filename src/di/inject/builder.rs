@@ -1,12 +1,37 @@
-use crate::{Activator, ServiceDependency, ServiceDescriptor, ServiceLifetime, Type};
+use crate::{
+    disposable::{tracker, Tracker},
+    r#type::RuntimeKey,
+    Activator, Disposable, Mut, ServiceDependency, ServiceDescriptor, ServiceLifetime, Type, ValueKey,
+};
+use std::any::Any;
 use std::sync::OnceLock;
 
+macro_rules! disposable {
+    ($($traits:tt)+) => {
+        /// Indicates the injected service is [disposable](Disposable).
+        ///
+        /// # Remarks
+        ///
+        /// `TSvc` must be the same service type the injected type is registered as, which is typically the trait the
+        /// implementation is injected as or the implementation itself. Otherwise, the service cannot be resolved and
+        /// resolution fails with [ResolveError::TypeMismatch](crate::ResolveError::TypeMismatch).
+        pub fn disposable<TSvc: Disposable + ?Sized + $($traits)+>(mut self) -> Self {
+            self.tracker = Some(tracker::<TSvc>());
+            self.tracker_mut = Some(tracker::<Mut<TSvc>>());
+            self
+        }
+    };
+}
+
 /// Represents the builder for an injected type.
 pub struct InjectBuilder {
     activator: Activator,
     lifetime: ServiceLifetime,
     key_type: Option<Type>,
+    value_key: Option<RuntimeKey>,
     dependencies: Vec<ServiceDependency>,
+    tracker: Option<Tracker>,
+    tracker_mut: Option<Tracker>,
 }
 
 impl InjectBuilder {
@@ -21,7 +46,10 @@ impl InjectBuilder {
             activator,
             lifetime,
             key_type: None,
+            value_key: None,
             dependencies: Vec::default(),
+            tracker: None,
+            tracker_mut: None,
         }
     }
 
@@ -40,6 +68,18 @@ impl InjectBuilder {
     /// Applies a key to the injected service.
     pub fn with_key<TKey>(mut self) -> Self {
         self.key_type = Some(Type::of::<TKey>());
+        self.value_key = None;
+        self
+    }
+
+    /// Applies a runtime value as the key of the injected service.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value used as the key of the service
+    pub fn with_value_key(mut self, key: impl ValueKey) -> Self {
+        self.value_key = Some(RuntimeKey::new(&key));
+        self.key_type = None;
         self
     }
 
@@ -49,12 +89,43 @@ impl InjectBuilder {
         self
     }
 
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async")] {
+            disposable!(Any + Send + Sync);
+
+            /// Indicates the injected service is [asynchronously disposable](crate::AsyncDisposable).
+            ///
+            /// # Remarks
+            ///
+            /// `TSvc` must be the same service type the injected type is registered as, which is typically the trait
+            /// the implementation is injected as or the implementation itself. Otherwise, the service cannot be
+            /// resolved and resolution fails with [ResolveError::TypeMismatch](crate::ResolveError::TypeMismatch).
+            /// Asynchronous disposal of a mutable service is not supported.
+            #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+            pub fn async_disposable<TSvc: crate::AsyncDisposable + ?Sized + Any>(mut self) -> Self {
+                self.tracker = Some(crate::disposable::async_tracker::<TSvc>());
+                self.tracker_mut = None;
+                self
+            }
+        } else {
+            disposable!(Any);
+        }
+    }
+
     /// Builds and returns a new [ServiceDescriptor].
     pub fn build(mut self) -> ServiceDescriptor {
+        let tracker = if self.activator.is_mutable() {
+            self.tracker_mut
+        } else {
+            self.tracker
+        };
+
         ServiceDescriptor::new(
             self.lifetime,
             if let Some(key) = self.key_type {
                 self.activator.service_type().with_key(&key)
+            } else if let Some(key) = self.value_key {
+                self.activator.service_type().with_value(&key)
             } else {
                 self.activator.service_type().clone()
             },
@@ -66,8 +137,9 @@ impl InjectBuilder {
                 self.dependencies
             },
             OnceLock::new(),
-            self.activator.factory(),
+            self.activator.activate(),
         )
+        .tracked_by(tracker)
     }
 }
 
@@ -1,47 +1,83 @@
-use crate::{KeyedRef, KeyedRefMut, Mut, Ref, RefMut, ServiceProvider};
+use crate::resolution::expect;
+use crate::{KeyedRef, KeyedRefMut, Mut, Ref, RefMut, ResolveError, ServiceProvider};
 use std::any::Any;
-use std::sync::OnceLock;
+use std::sync::{Mutex, OnceLock, PoisonError};
 
 /// Represents a holder for lazily-initialized service resolution.
 pub struct Lazy<T> {
     services: ServiceProvider,
-    resolve: fn(&ServiceProvider) -> T,
+    resolve: fn(&ServiceProvider) -> Result<T, ResolveError>,
     value: OnceLock<T>,
+
+    // a OnceLock cannot be initialized by a function that fails so resolution is
+    // serialized by a lock and the value is only set when resolution succeeds
+    lock: Mutex<()>,
 }
 
 impl<T> Lazy<T> {
-    fn new(services: ServiceProvider, resolve: fn(&ServiceProvider) -> T) -> Self {
+    fn new(services: ServiceProvider, resolve: fn(&ServiceProvider) -> Result<T, ResolveError>) -> Self {
         Self {
             services,
             resolve,
             value: OnceLock::new(),
+            lock: Mutex::default(),
         }
     }
 
     /// Resolves and returns a reference to the underlying, lazy-initialized service.
+    ///
+    /// # Panics
+    ///
+    /// The underlying service cannot be resolved.
+    #[inline]
     pub fn value(&self) -> &T {
-        self.value.get_or_init(|| (self.resolve)(&self.services))
+        expect(self.try_value())
+    }
+
+    /// Attempts to resolve and return a reference to the underlying, lazy-initialized service.
+    ///
+    /// # Remarks
+    ///
+    /// If the underlying service cannot be resolved, the [error](ResolveError) is returned instead of panicking and
+    /// a subsequent call attempts to resolve the service again.
+    pub fn try_value(&self) -> Result<&T, ResolveError> {
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let _lock = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(value) = self.value.get() {
+            return Ok(value);
+        }
+
+        let value = (self.resolve)(&self.services)?;
+        Ok(self.value.get_or_init(|| value))
     }
 }
 
 #[inline]
-fn to_vec<T: Any + ?Sized>(services: &ServiceProvider) -> Vec<Ref<T>> {
-    services.get_all::<T>().collect()
+fn to_vec<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<Ref<T>>, ResolveError> {
+    services.try_get_all::<T>()
 }
 
 #[inline]
-fn to_vec_mut<T: Any + ?Sized>(services: &ServiceProvider) -> Vec<RefMut<T>> {
-    services.get_all_mut::<T>().collect()
+fn to_vec_mut<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<RefMut<T>>, ResolveError> {
+    services.try_get_all_mut::<T>()
 }
 
 #[inline]
-fn to_keyed_vec<TKey, TSvc: Any + ?Sized>(services: &ServiceProvider) -> Vec<KeyedRef<TKey, TSvc>> {
-    services.get_all_by_key::<TKey, TSvc>().collect()
+fn to_keyed_vec<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRef<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, TSvc>()
 }
 
 #[inline]
-fn to_keyed_vec_mut<TKey, TSvc: Any + ?Sized>(services: &ServiceProvider) -> Vec<KeyedRefMut<TKey, TSvc>> {
-    services.get_all_by_key_mut::<TKey, TSvc>().collect()
+fn to_keyed_vec_mut<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRefMut<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, Mut<TSvc>>()
 }
 
 /// Creates and returns a holder for a lazily-initialized, required service.
@@ -51,7 +87,7 @@ fn to_keyed_vec_mut<TKey, TSvc: Any + ?Sized>(services: &ServiceProvider) -> Vec
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn exactly_one<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Ref<T>> {
-    Lazy::new(services, ServiceProvider::get_required::<T>)
+    Lazy::new(services, ServiceProvider::try_get_required::<T>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, required, mutable service.
@@ -61,7 +97,7 @@ pub fn exactly_one<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Ref<T>> {
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn exactly_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<RefMut<T>> {
-    Lazy::new(services, ServiceProvider::get_required_mut::<T>)
+    Lazy::new(services, ServiceProvider::try_get_required_mut::<T>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, keyed, required service.
@@ -71,7 +107,7 @@ pub fn exactly_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<RefMu
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn exactly_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Lazy<KeyedRef<TKey, TSvc>> {
-    Lazy::new(services, ServiceProvider::get_required_by_key::<TKey, TSvc>)
+    Lazy::new(services, ServiceProvider::try_get_required_by_key::<TKey, TSvc>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, keyed, required, mutable service.
@@ -81,7 +117,7 @@ pub fn exactly_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider)
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn exactly_one_with_key_mut<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Lazy<KeyedRefMut<TKey, TSvc>> {
-    Lazy::new(services, ServiceProvider::get_required_by_key_mut::<TKey, TSvc>)
+    Lazy::new(services, ServiceProvider::try_get_required_by_key_mut::<TKey, TSvc>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, optional service.
@@ -91,7 +127,7 @@ pub fn exactly_one_with_key_mut<TKey, TSvc: Any + ?Sized>(services: ServiceProvi
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn zero_or_one<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Option<Ref<T>>> {
-    Lazy::new(services, ServiceProvider::get::<T>)
+    Lazy::new(services, ServiceProvider::try_get::<T>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, optional, mutable service.
@@ -101,7 +137,7 @@ pub fn zero_or_one<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Option<Re
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn zero_or_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Option<RefMut<T>>> {
-    Lazy::new(services, ServiceProvider::get_mut::<T>)
+    Lazy::new(services, ServiceProvider::try_get::<Mut<T>>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, keyed, optional service.
@@ -111,7 +147,7 @@ pub fn zero_or_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Lazy<Optio
 /// * `services` - The [ServiceProvider] used to resolve the service
 #[inline]
 pub fn zero_or_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Lazy<Option<KeyedRef<TKey, TSvc>>> {
-    Lazy::new(services, ServiceProvider::get_by_key::<TKey, TSvc>)
+    Lazy::new(services, ServiceProvider::try_get_by_key::<TKey, TSvc>)
 }
 
 /// Creates and returns a holder for a lazily-initialized, keyed, optional, mutable service.
@@ -123,7 +159,7 @@ pub fn zero_or_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider)
 pub fn zero_or_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
     services: ServiceProvider,
 ) -> Lazy<Option<KeyedRefMut<TKey, TSvc>>> {
-    Lazy::new(services, ServiceProvider::get_by_key_mut::<TKey, TSvc>)
+    Lazy::new(services, ServiceProvider::try_get_by_key::<TKey, Mut<TSvc>>)
 }
 
 /// Creates and returns a holder for multiple, lazily-initialized services.
@@ -171,13 +207,16 @@ pub fn zero_or_more_with_key_mut<TKey, TSvc: Any + ?Sized>(
 /// Creates and return a holder for a lazy-initialized, optional service that is missing.
 #[inline]
 pub fn missing<T: Any + ?Sized>() -> Lazy<Option<Ref<T>>> {
-    Lazy::new(ServiceProvider::default(), ServiceProvider::get::<T>)
+    Lazy::new(ServiceProvider::default(), ServiceProvider::try_get::<T>)
 }
 
 /// Creates and return a holder for a lazy-initialized, keyed, optional service that is missing.
 #[inline]
 pub fn missing_with_key<TKey, TSvc: Any + ?Sized>() -> Lazy<Option<KeyedRef<TKey, TSvc>>> {
-    Lazy::new(ServiceProvider::default(), ServiceProvider::get_by_key::<TKey, TSvc>)
+    Lazy::new(
+        ServiceProvider::default(),
+        ServiceProvider::try_get_by_key::<TKey, TSvc>,
+    )
 }
 
 /// Creates and return a holder for any empty collection of lazy-initialized services.
@@ -202,6 +241,7 @@ pub fn init<T: Any + ?Sized>(instance: Box<T>) -> Lazy<Ref<T>> {
         resolve: |_| unimplemented!(),
         services: ServiceProvider::default(),
         value: OnceLock::from(Ref::from(instance)),
+        lock: Mutex::default(),
     }
 }
 
@@ -215,6 +255,7 @@ pub fn init_mut<T: Any + ?Sized>(instance: Box<Mut<T>>) -> Lazy<RefMut<T>> {
         resolve: |_| unimplemented!(),
         services: ServiceProvider::default(),
         value: OnceLock::from(RefMut::from(instance)),
+        lock: Mutex::default(),
     }
 }
 
@@ -228,6 +269,7 @@ pub fn init_with_key<TKey, TSvc: Any + ?Sized>(instance: Box<TSvc>) -> Lazy<Keye
         resolve: |_| unimplemented!(),
         services: ServiceProvider::default(),
         value: OnceLock::from(KeyedRef::<TKey, TSvc>::new(Ref::from(instance))),
+        lock: Mutex::default(),
     }
 }
 
@@ -241,6 +283,7 @@ pub fn init_with_key_mut<TKey, TSvc: Any + ?Sized>(instance: Box<Mut<TSvc>>) ->
         resolve: |_| unimplemented!(),
         services: ServiceProvider::default(),
         value: OnceLock::from(KeyedRefMut::<TKey, TSvc>::new(Ref::from(instance))),
+        lock: Mutex::default(),
     }
 }
 
@@ -306,7 +349,7 @@ mod tests {
     fn lazy_should_return_required_service() {
         // arrange
         let provider = ServiceCollection::new()
-            .add(transient_as_self::<Bar>().from(|_| Ref::new(Bar::default())))
+            .add(transient_as_self::<Bar>().from(|_| Ref::new(Bar)))
             .add(
                 transient_as_self::<Foo>()
                     .depends_on(crate::exactly_one::<Bar>())
@@ -326,7 +369,7 @@ mod tests {
     fn lazy_should_return_optional_service() {
         // arrange
         let provider = ServiceCollection::new()
-            .add(transient_as_self::<Bar>().from(|_| Ref::new(Bar::default())))
+            .add(transient_as_self::<Bar>().from(|_| Ref::new(Bar)))
             .add(
                 transient_as_self::<Foo2>()
                     .depends_on(crate::zero_or_one::<Bar>())
@@ -386,11 +429,11 @@ mod tests {
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
+    #[allow(ambiguous_wide_pointer_comparisons)]
     fn lazy_should_return_same_scoped_service() {
         // arrange
         let provider = ServiceCollection::new()
-            .add(scoped_factory(|_| Ref::new(Bar::default())))
+            .add(scoped_factory(|_| Ref::new(Bar)))
             .add(
                 transient_as_self::<Foo>()
                     .depends_on(crate::exactly_one::<Bar>())
@@ -398,11 +441,12 @@ mod tests {
             )
             .build_provider()
             .unwrap();
+        let scope = provider.create_scope();
 
         // act
-        let foo = provider.get_required::<Foo>();
-        let bar1 = provider.get_required::<Bar>();
-        let bar2 = provider.clone().get_required::<Bar>();
+        let foo = scope.get_required::<Foo>();
+        let bar1 = scope.get_required::<Bar>();
+        let bar2 = scope.clone().get_required::<Bar>();
 
         // assert
         assert!(Ref::ptr_eq(foo.bar.value(), &bar1));
@@ -468,4 +512,17 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn try_value_should_return_error_when_service_cannot_be_resolved() {
+        // arrange
+        let provider = ServiceCollection::new().build_provider().unwrap();
+        let lazy = lazy::exactly_one::<Bar>(provider);
+
+        // act
+        let result = lazy.try_value();
+
+        // assert
+        assert!(matches!(result, Err(ResolveError::Missing { .. })));
+    }
 }
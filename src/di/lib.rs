@@ -40,19 +40,61 @@ pub type RefMut<T> = Ref<Mut<T>>;
 mod collection;
 mod dependency;
 mod description;
-pub(crate) mod fmt;
+mod disposable;
+mod eager;
+/// Contains support for rendering the services in a collection.
+pub mod fmt;
+mod generic;
+mod hosted;
 mod keyed;
+#[cfg(feature = "serde")]
+mod manifest;
+mod observer;
 mod provider;
+mod resolution;
+mod strict;
 mod r#type;
 mod validation;
 
 pub use collection::ServiceCollection;
 pub use dependency::{ServiceCardinality, ServiceDependency};
 pub use description::{ServiceDescriptor, ServiceLifetime};
+pub use disposable::Disposable;
+pub use generic::OpenGeneric;
+pub use hosted::{HostError, HostedService};
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use disposable::{AsyncDisposable, DisposeError, DisposeFuture};
+
+#[cfg(feature = "async")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+pub use hosted::{AsyncHostedService, HostFuture};
+
 pub use keyed::{KeyedRef, KeyedRefMut};
+
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use manifest::{DependencyRecord, Manifest, ServiceRecord, ValidationRecord};
+
+pub use observer::{ResolutionEvent, ResolutionObserver};
+
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub use observer::TracingObserver;
+
+#[cfg(feature = "log")]
+#[cfg_attr(docsrs, doc(cfg(feature = "log")))]
+pub use observer::LogObserver;
+
 pub use provider::{ScopedServiceProvider, ServiceProvider};
-pub use r#type::Type;
-pub use validation::{validate, ValidationError};
+pub use r#type::{Type, ValueKey};
+pub use resolution::ResolveError;
+pub use strict::UndeclaredDependency;
+pub use validation::{
+    validate, validate_with, ValidationContext, ValidationError, ValidationOptions, ValidationResult, ValidationRule,
+    ValidationSeverity,
+};
 
 cfg_if! {
     if #[cfg(feature = "builder")] {
@@ -60,11 +102,13 @@ cfg_if! {
 
         #[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
         pub use builder::{
-            exactly_one, exactly_one_with_key, existing, existing_as_self, existing_with_key, existing_with_key_as_self,
-            scoped, scoped_factory, scoped_with_key, scoped_with_key_factory, singleton, singleton_as_self,
-            singleton_factory, singleton_with_key, singleton_with_key_factory, transient, transient_as_self,
-            transient_factory, transient_with_key, transient_with_key_as_self, transient_with_key_factory, zero_or_more,
-            zero_or_more_with_key, zero_or_one, zero_or_one_with_key,
+            exactly_one, exactly_one_generic, exactly_one_with_key, exactly_one_with_value_key, existing, existing_as_self, existing_with_key,
+            existing_with_key_as_self, scoped, scoped_factory, scoped_generic, scoped_with_key, scoped_with_key_factory,
+            scoped_with_value_key, singleton, singleton_as_self, singleton_factory, singleton_generic,
+            singleton_with_key, singleton_with_key_factory, singleton_with_value_key, transient, transient_as_self,
+            transient_factory, transient_generic, transient_with_key, transient_with_key_as_self,
+            transient_with_key_factory, transient_with_value_key, zero_or_more, zero_or_more_with_key,
+            zero_or_more_with_value_key, zero_or_one, zero_or_one_generic, zero_or_one_with_key, zero_or_one_with_value_key,
         };
 
         #[cfg_attr(docsrs, doc(cfg(feature = "builder")))]
@@ -89,5 +133,13 @@ cfg_if! {
 #[cfg(feature = "lazy")]
 pub mod lazy;
 
+/// Contains support for factory service resolution.
+#[cfg(feature = "factory")]
+pub mod factory;
+
+/// Contains support for resolving a service within its own scope.
+#[cfg(feature = "owned")]
+pub mod owned;
+
 #[cfg(test)]
 mod test;
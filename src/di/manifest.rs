@@ -0,0 +1,146 @@
+use crate::{
+    validation, ServiceCardinality, ServiceCollection, ServiceDependency, ServiceDescriptor, ServiceLifetime, Type,
+    ValidationOptions, ValidationResult, ValidationSeverity,
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents a machine-readable manifest of the services registered in a [ServiceCollection].
+///
+/// # Remarks
+///
+/// The schema of a manifest is versioned. Any change to the schema that is not backward compatible increments the
+/// [version](Manifest::VERSION). Services, dependencies, and validation results appear in the order in which they
+/// were registered, declared, and reported, which makes the manifests of two compositions suitable for comparison.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the manifest schema.
+    pub version: u32,
+
+    /// The registered services.
+    pub services: Vec<ServiceRecord>,
+
+    /// The results of validating the registered services.
+    pub validation: Vec<ValidationRecord>,
+}
+
+impl Manifest {
+    /// The current version of the manifest schema.
+    pub const VERSION: u32 = 1;
+
+    /// Initializes a new manifest.
+    ///
+    /// # Arguments
+    ///
+    /// * `services` - The [service collection](ServiceCollection) to create the manifest for
+    /// * `options` - The [validation options](ValidationOptions) used to validate the services
+    pub fn new(services: &ServiceCollection, options: &ValidationOptions) -> Self {
+        let validation = validation::evaluate(services, options);
+
+        Self {
+            version: Self::VERSION,
+            services: services.iter().map(ServiceRecord::from).collect(),
+            validation: validation.iter().map(ValidationRecord::from).collect(),
+        }
+    }
+}
+
+/// Represents a registered service in a [Manifest].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ServiceRecord {
+    /// The name of the service type.
+    pub service_type: String,
+
+    /// The name of the implementation type.
+    pub implementation_type: String,
+
+    /// The key of the service, if any.
+    pub key: Option<String>,
+
+    /// The lifetime of the service.
+    pub lifetime: ServiceLifetime,
+
+    /// The declared dependencies of the service.
+    pub dependencies: Vec<DependencyRecord>,
+}
+
+impl From<&ServiceDescriptor> for ServiceRecord {
+    fn from(descriptor: &ServiceDescriptor) -> Self {
+        let (service_type, key) = deconstruct(descriptor.service_type());
+
+        Self {
+            service_type,
+            implementation_type: String::from(descriptor.implementation_type().name()),
+            key,
+            lifetime: descriptor.lifetime(),
+            dependencies: descriptor
+                .dependencies()
+                .iter()
+                .filter(|dependency| !dependency.is_provided())
+                .map(DependencyRecord::from)
+                .collect(),
+        }
+    }
+}
+
+/// Represents a declared dependency in a [Manifest].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DependencyRecord {
+    /// The name of the injected type.
+    pub injected_type: String,
+
+    /// The key of the injected type, if any.
+    pub key: Option<String>,
+
+    /// The cardinality of the dependency.
+    pub cardinality: ServiceCardinality,
+}
+
+impl From<&ServiceDependency> for DependencyRecord {
+    fn from(dependency: &ServiceDependency) -> Self {
+        let (injected_type, key) = deconstruct(dependency.injected_type());
+
+        Self {
+            injected_type,
+            key,
+            cardinality: dependency.cardinality(),
+        }
+    }
+}
+
+/// Represents a validation result in a [Manifest].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidationRecord {
+    /// The identifier of the rule that produced the result.
+    pub rule: String,
+
+    /// The severity of the result.
+    pub severity: ValidationSeverity,
+
+    /// The name of the offending service type.
+    pub service_type: String,
+
+    /// The key of the offending service, if any.
+    pub key: Option<String>,
+
+    /// The validation message.
+    pub message: String,
+}
+
+impl From<&ValidationResult> for ValidationRecord {
+    fn from(result: &ValidationResult) -> Self {
+        let (service_type, key) = deconstruct(result.service_type());
+
+        Self {
+            rule: String::from(result.rule()),
+            severity: result.severity(),
+            service_type,
+            key,
+            message: String::from(result.message()),
+        }
+    }
+}
+
+fn deconstruct(ty: &Type) -> (String, Option<String>) {
+    let (name, key) = Type::deconstruct(ty);
+    (String::from(name), key.map(String::from))
+}
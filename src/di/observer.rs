@@ -0,0 +1,186 @@
+use crate::r#type::expand_type;
+use crate::{ServiceDescriptor, ServiceLifetime, Type};
+use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::time::Duration;
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        pub(crate) type Observer = dyn ResolutionObserver + Send + Sync;
+    } else {
+        pub(crate) type Observer = dyn ResolutionObserver;
+    }
+}
+
+/// Represents the resolution of a service by a [ServiceProvider](crate::ServiceProvider).
+pub struct ResolutionEvent<'a> {
+    descriptor: &'a ServiceDescriptor,
+    cached: bool,
+    duration: Duration,
+    parent: Option<Type>,
+}
+
+impl<'a> ResolutionEvent<'a> {
+    pub(crate) fn new(
+        descriptor: &'a ServiceDescriptor,
+        cached: bool,
+        duration: Duration,
+        parent: Option<Type>,
+    ) -> Self {
+        Self {
+            descriptor,
+            cached,
+            duration,
+            parent,
+        }
+    }
+
+    /// Gets the [type](Type) of the resolved service.
+    #[inline]
+    pub fn service_type(&self) -> &Type {
+        self.descriptor.service_type()
+    }
+
+    /// Gets the [descriptor](ServiceDescriptor) used to resolve the service.
+    #[inline]
+    pub fn descriptor(&self) -> &ServiceDescriptor {
+        self.descriptor
+    }
+
+    /// Gets the [lifetime](ServiceLifetime) of the resolved service.
+    #[inline]
+    pub fn lifetime(&self) -> ServiceLifetime {
+        self.descriptor.lifetime()
+    }
+
+    /// Gets a value indicating whether the service was resolved from a cached instance rather than activated.
+    #[inline]
+    pub fn is_cached(&self) -> bool {
+        self.cached
+    }
+
+    /// Gets the amount of time it took to resolve the service.
+    ///
+    /// # Remarks
+    ///
+    /// The duration of an activated service includes the time it took to resolve all of its dependencies.
+    #[inline]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Gets the [type](Type) of the service being activated when the service was resolved, if any.
+    #[inline]
+    pub fn parent(&self) -> Option<&Type> {
+        self.parent.as_ref()
+    }
+}
+
+impl Display for ResolutionEvent<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "Resolved service {} with implementation '{}' [{:?}]",
+            expand_type(self.service_type()),
+            self.descriptor.implementation_type().name(),
+            self.lifetime()
+        )?;
+
+        if self.cached {
+            formatter.write_str(" from cache")?;
+        } else {
+            write!(formatter, " in {:?}", self.duration)?;
+        }
+
+        if let Some(parent) = &self.parent {
+            write!(formatter, " for {}", expand_type(parent))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Defines the behavior of an observer of the services resolved by a [ServiceProvider](crate::ServiceProvider).
+///
+/// # Remarks
+///
+/// An observer is notified synchronously on the thread that resolved the service so it should return quickly. Any
+/// function that accepts a [ResolutionEvent] is also an observer.
+///
+/// # Example
+///
+/// ```
+/// use di::*;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// #[injectable]
+/// struct Clock;
+///
+/// let activations = Arc::new(AtomicUsize::new(0));
+/// let counter = activations.clone();
+/// let provider = ServiceCollection::new()
+///     .add(Clock::transient())
+///     .build_provider()
+///     .unwrap()
+///     .with_observer(move |event: &ResolutionEvent| {
+///         if !event.is_cached() {
+///             counter.fetch_add(1, Ordering::Relaxed);
+///         }
+///     });
+///
+/// let _ = provider.get_required::<Clock>();
+/// let _ = provider.get_required::<Clock>();
+///
+/// assert_eq!(activations.load(Ordering::Relaxed), 2);
+/// ```
+pub trait ResolutionObserver {
+    /// Occurs when a service has been resolved.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The [event](ResolutionEvent) that describes the resolution
+    fn on_resolved(&self, event: &ResolutionEvent);
+}
+
+impl<F: Fn(&ResolutionEvent)> ResolutionObserver for F {
+    #[inline]
+    fn on_resolved(&self, event: &ResolutionEvent) {
+        self(event)
+    }
+}
+
+/// Represents a [resolution observer](ResolutionObserver) that emits a [tracing](https://docs.rs/tracing) event at
+/// the trace level for every resolution.
+#[cfg(feature = "tracing")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TracingObserver;
+
+#[cfg(feature = "tracing")]
+impl ResolutionObserver for TracingObserver {
+    fn on_resolved(&self, event: &ResolutionEvent) {
+        tracing::trace!(
+            target: "di",
+            service = %event.service_type(),
+            implementation = event.descriptor().implementation_type().name(),
+            lifetime = ?event.lifetime(),
+            cached = event.is_cached(),
+            duration = ?event.duration(),
+            parent = event.parent().map(Type::to_string),
+            "{}",
+            event
+        );
+    }
+}
+
+/// Represents a [resolution observer](ResolutionObserver) that writes a [log](https://docs.rs/log) record at the
+/// trace level for every resolution.
+#[cfg(feature = "log")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LogObserver;
+
+#[cfg(feature = "log")]
+impl ResolutionObserver for LogObserver {
+    fn on_resolved(&self, event: &ResolutionEvent) {
+        log::trace!(target: "di", "{}", event);
+    }
+}
@@ -0,0 +1,489 @@
+use crate::resolution::expect;
+use crate::{KeyedRef, KeyedRefMut, Mut, Ref, RefMut, ResolveError, ServiceProvider};
+use std::any::Any;
+use std::ops::Deref;
+
+/// Represents a service resolved within its own scope.
+///
+/// # Remarks
+///
+/// A new scope is created when the service is resolved and the scope is owned by the value. Every scoped service
+/// resolved for the service belongs to the new scope, which makes it possible for a singleton or transient service to
+/// depend on a unit of work with scoped dependencies. When the value is dropped, the scope is dropped with it and the
+/// [disposable](crate::Disposable) services it owns are disposed.
+pub struct Owned<T> {
+    // the value is declared first so that it is dropped before its scope
+    value: T,
+    scope: ServiceProvider,
+}
+
+impl<T> Owned<T> {
+    fn try_new(
+        services: ServiceProvider,
+        resolve: fn(&ServiceProvider) -> Result<T, ResolveError>,
+    ) -> Result<Self, ResolveError> {
+        let scope = services.create_scope();
+        let value = resolve(&scope)?;
+        Ok(Self { value, scope })
+    }
+
+    /// Gets the underlying service.
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Gets the [scope](ServiceProvider) owned by the service.
+    #[inline]
+    pub fn scope(&self) -> &ServiceProvider {
+        &self.scope
+    }
+}
+
+impl<T> Deref for Owned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+#[inline]
+fn to_vec<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<Ref<T>>, ResolveError> {
+    services.try_get_all::<T>()
+}
+
+#[inline]
+fn to_vec_mut<T: Any + ?Sized>(services: &ServiceProvider) -> Result<Vec<RefMut<T>>, ResolveError> {
+    services.try_get_all_mut::<T>()
+}
+
+#[inline]
+fn to_keyed_vec<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRef<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, TSvc>()
+}
+
+#[inline]
+fn to_keyed_vec_mut<TKey, TSvc: Any + ?Sized>(
+    services: &ServiceProvider,
+) -> Result<Vec<KeyedRefMut<TKey, TSvc>>, ResolveError> {
+    services.try_get_all_by_key::<TKey, Mut<TSvc>>()
+}
+
+/// Creates and returns a required service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn exactly_one<T: Any + ?Sized>(services: ServiceProvider) -> Owned<Ref<T>> {
+    expect(try_exactly_one::<T>(services))
+}
+
+/// Attempts to create and return a required service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_exactly_one<T: Any + ?Sized>(services: ServiceProvider) -> Result<Owned<Ref<T>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_required::<T>)
+}
+
+/// Creates and returns a required, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn exactly_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Owned<RefMut<T>> {
+    expect(try_exactly_one_mut::<T>(services))
+}
+
+/// Attempts to create and return a required, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_exactly_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Result<Owned<RefMut<T>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_required_mut::<T>)
+}
+
+/// Creates and returns a keyed, required service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn exactly_one_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Owned<KeyedRef<TKey, TSvc>> {
+    expect(try_exactly_one_with_key::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return a keyed, required service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_exactly_one_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<KeyedRef<TKey, TSvc>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_required_by_key::<TKey, TSvc>)
+}
+
+/// Creates and returns a keyed, required, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn exactly_one_with_key_mut<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Owned<KeyedRefMut<TKey, TSvc>> {
+    expect(try_exactly_one_with_key_mut::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return a keyed, required, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_exactly_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<KeyedRefMut<TKey, TSvc>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_required_by_key_mut::<TKey, TSvc>)
+}
+
+/// Creates and returns an optional service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn zero_or_one<T: Any + ?Sized>(services: ServiceProvider) -> Owned<Option<Ref<T>>> {
+    expect(try_zero_or_one::<T>(services))
+}
+
+/// Attempts to create and return an optional service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_one<T: Any + ?Sized>(services: ServiceProvider) -> Result<Owned<Option<Ref<T>>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get::<T>)
+}
+
+/// Creates and returns an optional, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn zero_or_one_mut<T: Any + ?Sized>(services: ServiceProvider) -> Owned<Option<RefMut<T>>> {
+    expect(try_zero_or_one_mut::<T>(services))
+}
+
+/// Attempts to create and return an optional, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_one_mut<T: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<Option<RefMut<T>>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get::<Mut<T>>)
+}
+
+/// Creates and returns a keyed, optional service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn zero_or_one_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Owned<Option<KeyedRef<TKey, TSvc>>> {
+    expect(try_zero_or_one_with_key::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return a keyed, optional service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_one_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<Option<KeyedRef<TKey, TSvc>>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_by_key::<TKey, TSvc>)
+}
+
+/// Creates and returns a keyed, optional, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// The service cannot be resolved.
+#[inline]
+pub fn zero_or_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Owned<Option<KeyedRefMut<TKey, TSvc>>> {
+    expect(try_zero_or_one_with_key_mut::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return a keyed, optional, mutable service resolved within its own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_one_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<Option<KeyedRefMut<TKey, TSvc>>>, ResolveError> {
+    Owned::try_new(services, ServiceProvider::try_get_by_key::<TKey, Mut<TSvc>>)
+}
+
+/// Creates and returns multiple services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// Any of the services cannot be resolved.
+#[inline]
+pub fn zero_or_more<T: Any + ?Sized>(services: ServiceProvider) -> Owned<Vec<Ref<T>>> {
+    expect(try_zero_or_more::<T>(services))
+}
+
+/// Attempts to create and return multiple services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_more<T: Any + ?Sized>(services: ServiceProvider) -> Result<Owned<Vec<Ref<T>>>, ResolveError> {
+    Owned::try_new(services, to_vec::<T>)
+}
+
+/// Creates and returns multiple, mutable services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// Any of the services cannot be resolved.
+#[inline]
+pub fn zero_or_more_mut<T: Any + ?Sized>(services: ServiceProvider) -> Owned<Vec<RefMut<T>>> {
+    expect(try_zero_or_more_mut::<T>(services))
+}
+
+/// Attempts to create and return multiple, mutable services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_more_mut<T: Any + ?Sized>(services: ServiceProvider) -> Result<Owned<Vec<RefMut<T>>>, ResolveError> {
+    Owned::try_new(services, to_vec_mut::<T>)
+}
+
+/// Creates and returns multiple, keyed services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// Any of the services cannot be resolved.
+#[inline]
+pub fn zero_or_more_with_key<TKey, TSvc: Any + ?Sized>(services: ServiceProvider) -> Owned<Vec<KeyedRef<TKey, TSvc>>> {
+    expect(try_zero_or_more_with_key::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return multiple, keyed services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_more_with_key<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<Vec<KeyedRef<TKey, TSvc>>>, ResolveError> {
+    Owned::try_new(services, to_keyed_vec::<TKey, TSvc>)
+}
+
+/// Creates and returns multiple, keyed, mutable services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+///
+/// # Panics
+///
+/// Any of the services cannot be resolved.
+#[inline]
+pub fn zero_or_more_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Owned<Vec<KeyedRefMut<TKey, TSvc>>> {
+    expect(try_zero_or_more_with_key_mut::<TKey, TSvc>(services))
+}
+
+/// Attempts to create and return multiple, keyed, mutable services resolved within their own scope.
+///
+/// # Arguments
+///
+/// * `services` - The [ServiceProvider] used to create the scope
+#[inline]
+pub fn try_zero_or_more_with_key_mut<TKey, TSvc: Any + ?Sized>(
+    services: ServiceProvider,
+) -> Result<Owned<Vec<KeyedRefMut<TKey, TSvc>>>, ResolveError> {
+    Owned::try_new(services, to_keyed_vec_mut::<TKey, TSvc>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{exactly_one, owned, scoped, singleton_as_self, test::*, transient_as_self, ServiceCollection};
+    use std::sync::Mutex;
+
+    struct Job {
+        work: Ref<Disposer>,
+    }
+
+    struct Worker {
+        job: Owned<Ref<Job>>,
+    }
+
+    fn new_services(log: &DisposeLog) -> ServiceCollection {
+        let log = log.clone();
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                scoped::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("work", log.clone()))),
+            )
+            .add(
+                transient_as_self::<Job>()
+                    .depends_on(exactly_one::<Disposer>())
+                    .from(|sp| {
+                        Ref::new(Job {
+                            work: sp.get_required::<Disposer>(),
+                        })
+                    }),
+            );
+
+        services
+    }
+
+    #[test]
+    fn owned_should_resolve_service_within_new_scope() {
+        // arrange
+        let provider = new_services(&DisposeLog::default()).build_provider().unwrap();
+        let scope = provider.create_scope();
+
+        // act
+        let job1 = owned::exactly_one::<Job>(scope.clone());
+        let job2 = owned::exactly_one::<Job>(scope.clone());
+
+        // assert
+        assert!(Ref::ptr_eq(&job1.work, &job1.scope().get_required::<Disposer>()));
+        assert!(!Ref::ptr_eq(&job1.work, &job2.work));
+        assert!(!Ref::ptr_eq(&job1.work, &scope.get_required::<Disposer>()));
+    }
+
+    #[test]
+    fn owned_should_dispose_scope_when_dropped() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let provider = new_services(&log).build_provider().unwrap();
+        let job = owned::exactly_one::<Job>(provider.clone());
+
+        // act
+        drop(job);
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["work"]);
+    }
+
+    #[test]
+    fn owned_should_resolve_scoped_service_for_singleton() {
+        // arrange
+        let mut services = new_services(&DisposeLog::default());
+
+        services.add(
+            singleton_as_self::<Worker>()
+                .depends_on(exactly_one::<Job>().as_scope_boundary())
+                .from(|sp| {
+                    Ref::new(Worker {
+                        job: owned::exactly_one::<Job>(sp.clone()),
+                    })
+                }),
+        );
+
+        let provider = services.build_provider().unwrap();
+
+        // act
+        let worker = provider.get_required::<Worker>();
+
+        // assert
+        assert!(Ref::ptr_eq(
+            &worker.job.work,
+            &worker.job.scope().get_required::<Disposer>()
+        ));
+    }
+
+    #[test]
+    fn try_exactly_one_should_return_error_when_service_cannot_be_resolved() {
+        // arrange
+        let provider = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let result = owned::try_exactly_one::<Job>(provider);
+
+        // assert
+        assert!(matches!(result, Err(ResolveError::Missing { .. })));
+    }
+}
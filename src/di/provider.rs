@@ -1,15 +1,139 @@
-use crate::{KeyedRef, KeyedRefMut, Mut, Ref, RefMut, ServiceDescriptor, Type};
-use std::any::{type_name, Any};
+use crate::description::{Activate, Instance};
+use crate::disposable::{Disposables, Owner, Tracked};
+use crate::eager;
+use crate::hosted;
+use crate::observer::Observer;
+use crate::resolution::expect;
+use crate::strict::Strict;
+use crate::{
+    validate_with, HostError, HostedService, KeyedRef, KeyedRefMut, Mut, OpenGeneric, Ref, RefMut, ResolutionObserver,
+    ResolveError, ServiceCollection, ServiceDependency, ServiceDescriptor, ServiceLifetime, Type, UndeclaredDependency,
+    ValidationError, ValidationOptions, ValueKey,
+};
+use std::any::Any;
 use std::borrow::Borrow;
-use std::collections::HashMap;
-use std::iter::empty;
+use std::collections::{HashMap, HashSet};
+use std::error::Error;
+use std::iter::successors;
 use std::marker::PhantomData;
 use std::ops::Deref;
+use std::sync::{Mutex, OnceLock, PoisonError};
+
+// closed generic descriptors are created on demand. singletons are shared by
+// every scope whereas other lifetimes are owned by the scope that closed them
+type ClosedGenerics = Mutex<HashMap<Type, Ref<ServiceDescriptor>>>;
+
+// a service is either registered or a closed generic type that is created on demand
+enum Found<'a> {
+    Registered(&'a ServiceDescriptor),
+    Closed(Ref<ServiceDescriptor>),
+}
+
+impl Deref for Found<'_> {
+    type Target = ServiceDescriptor;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Registered(descriptor) => descriptor,
+            Self::Closed(descriptor) => descriptor,
+        }
+    }
+}
+
+macro_rules! generic {
+    ($($traits:tt)+) => {
+        /// Gets a service of the specified closed generic type.
+        ///
+        /// # Remarks
+        ///
+        /// A service registered for the closed type takes precedence. Otherwise, the service is activated from the
+        /// registration of its open generic type definition, if any, using the [OpenGeneric] implementation of `T`.
+        /// Refer to [get](Self::get) for more information.
+        pub fn get_generic<T: OpenGeneric + ?Sized + $($traits)+>(&self) -> Option<Ref<T>> {
+            let (provider, descriptor) = self.lookup_generic::<T>()?;
+            self.activate_optional(provider, &descriptor)
+        }
+
+        /// Gets a required service of the specified closed generic type.
+        ///
+        /// # Panics
+        ///
+        /// The requested service of type `T` does not exist.
+        pub fn get_required_generic<T: OpenGeneric + ?Sized + $($traits)+>(&self) -> Ref<T> {
+            expect(self.try_get_required_generic::<T>())
+        }
+
+        /// Attempts to get a required service of the specified closed generic type.
+        ///
+        /// # Remarks
+        ///
+        /// Refer to [try_get_required](Self::try_get_required) for more information.
+        pub fn try_get_required_generic<T: OpenGeneric + ?Sized + $($traits)+>(&self) -> Result<Ref<T>, ResolveError> {
+            let Some((provider, descriptor)) = self.lookup_generic::<T>() else {
+                return Err(ResolveError::missing(Type::of::<T>()));
+            };
+
+            self.activate(provider, &descriptor)
+        }
+
+        // any closed type of a registered open generic type definition can be closed by the OpenGeneric implementation
+        fn lookup_generic<T: OpenGeneric + ?Sized + $($traits)+>(&self) -> Option<(&Self, Found<'_>)> {
+            self.lookup_or_close(&Type::of::<T>(), || {
+                Some((Type::definition_of::<T>(), Ref::new(|sp| Ok(Ref::new(T::activate(sp))))))
+            })
+        }
+    };
+}
+
+macro_rules! observer {
+    ($($bounds:tt)+) => {
+        /// Returns the provider with an observer that is notified of every service it resolves.
+        ///
+        /// # Arguments
+        ///
+        /// * `observer` - The [observer](ResolutionObserver) notified of each resolution
+        ///
+        /// # Remarks
+        ///
+        /// The observer is shared by every [scope](Self::create_scope) and [child](Self::create_child) subsequently
+        /// created from the provider. The observer replaces any previous observer. Observation has no overhead when a
+        /// provider does not have an observer.
+        pub fn with_observer(self, observer: impl ResolutionObserver + $($bounds)+) -> Self {
+            Self {
+                observer: Some(Ref::new(observer)),
+                ..self
+            }
+        }
+    };
+}
 
 /// Represents a service provider.
+///
+/// # Remarks
+///
+/// Cloning a service provider is shallow and the clone shares the same scope. When the last clone of a scope is
+/// dropped, the [disposable](crate::Disposable) services it owns are disposed in the reverse order of their creation.
+/// The provider supplied to a service factory, which is also the provider captured by an injected [ServiceProvider],
+/// [Lazy](crate::lazy::Lazy), [Factory](crate::factory::Factory), or [Owned](crate::owned::Owned), does not own its
+/// scope. A service can retain such a provider without preventing the disposal of the scope it belongs to, but it
+/// should not use the provider to resolve services after the scope has been disposed.
 #[derive(Clone)]
 pub struct ServiceProvider {
     services: Ref<HashMap<Type, Vec<ServiceDescriptor>>>,
+    instances: Ref<[Instance]>,
+    parent: Option<Ref<ServiceProvider>>,
+    strict: Option<Ref<Strict>>,
+    options: Ref<ValidationOptions>,
+    validate_scopes: bool,
+    scoped: bool,
+    singleton: Option<Type>,
+    observer: Option<Ref<Observer>>,
+    disposables: Ref<Disposables>,
+    root: Ref<Disposables>,
+    owner: Option<Ref<Owner>>,
+    generics: Ref<ClosedGenerics>,
+    root_generics: Ref<ClosedGenerics>,
+    closers: Ref<HashMap<Type, ServiceDependency>>,
 }
 
 impl ServiceProvider {
@@ -18,23 +142,81 @@ impl ServiceProvider {
     /// # Arguments
     ///
     /// * `services` - The map of [service descriptors](ServiceDescriptor) encapsulated by the provider
-    pub fn new(services: HashMap<Type, Vec<ServiceDescriptor>>) -> Self {
+    pub fn new(mut services: HashMap<Type, Vec<ServiceDescriptor>>) -> Self {
+        let mut slots = 0;
+
+        for descriptor in services.values_mut().flatten() {
+            if descriptor.lifetime() == ServiceLifetime::Scoped {
+                descriptor.set_slot(slots);
+                slots += 1;
+            }
+        }
+
+        let mut closers = HashMap::new();
+
+        for dependency in services.values().flatten().flat_map(ServiceDescriptor::dependencies) {
+            if dependency.activator().is_some() {
+                closers
+                    .entry(dependency.injected_type().clone())
+                    .or_insert_with(|| dependency.clone());
+            }
+        }
+
+        let disposables = Ref::new(Disposables::default());
+        let generics = Ref::new(ClosedGenerics::default());
+
         Self {
             services: Ref::new(services),
+            instances: new_instances(slots),
+            parent: None,
+            strict: None,
+            options: Ref::new(ValidationOptions::default().validate_scopes(false)),
+            validate_scopes: false,
+            scoped: false,
+            singleton: None,
+            observer: None,
+            root: disposables.clone(),
+            owner: Some(Ref::new(Owner::new(disposables.clone(), None))),
+            disposables,
+            root_generics: generics.clone(),
+            generics,
+            closers: Ref::new(closers),
         }
     }
 
-    /// Gets a service of the specified type.
-    pub fn get<T: Any + ?Sized>(&self) -> Option<Ref<T>> {
-        let key = Type::of::<T>();
+    pub(crate) fn with_options(
+        services: HashMap<Type, Vec<ServiceDescriptor>>,
+        options: Ref<ValidationOptions>,
+    ) -> Self {
+        let strict = if options.is_strict() {
+            Some(Ref::new(Strict::new(services.values().flatten())))
+        } else {
+            None
+        };
 
-        if let Some(descriptors) = self.services.get(&key) {
-            if let Some(descriptor) = descriptors.last() {
-                return Some(descriptor.get(self).downcast_ref::<Ref<T>>().unwrap().clone());
-            }
+        Self {
+            strict,
+            validate_scopes: options.validates_scopes(),
+            options,
+            ..Self::new(services)
         }
+    }
 
-        None
+    /// Gets a service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// `None` is only returned when the service is not registered. Use [try_get_required](Self::try_get_required) to
+    /// handle a failure without panicking.
+    ///
+    /// # Panics
+    ///
+    /// The service is registered, but it or one of its dependencies cannot be activated. This includes a scoped
+    /// service requested from the root provider or by a singleton when
+    /// [scopes are validated](ValidationOptions::validate_scopes).
+    pub fn get<T: Any + ?Sized>(&self) -> Option<Ref<T>> {
+        let (provider, descriptor) = self.lookup(&Type::of::<T>())?;
+        self.activate_optional(provider, &descriptor)
     }
 
     /// Gets a mutable service of the specified type.
@@ -44,18 +226,13 @@ impl ServiceProvider {
     }
 
     /// Gets a keyed service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [get](Self::get) for more information.
     pub fn get_by_key<TKey, TSvc: Any + ?Sized>(&self) -> Option<KeyedRef<TKey, TSvc>> {
-        let key = Type::keyed::<TKey, TSvc>();
-
-        if let Some(descriptors) = self.services.get(&key) {
-            if let Some(descriptor) = descriptors.last() {
-                return Some(KeyedRef::new(
-                    descriptor.get(self).downcast_ref::<Ref<TSvc>>().unwrap().clone(),
-                ));
-            }
-        }
-
-        None
+        let (provider, descriptor) = self.lookup(&Type::keyed::<TKey, TSvc>())?;
+        self.activate_optional(provider, &descriptor).map(KeyedRef::new)
     }
 
     /// Gets a keyed, mutable service of the specified type.
@@ -64,15 +241,56 @@ impl ServiceProvider {
         self.get_by_key::<TKey, Mut<TSvc>>()
     }
 
+    /// Gets a service of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the service is keyed by
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [get](Self::get) for more information.
+    pub fn get_by_value_key<TSvc: Any + ?Sized>(&self, key: &(impl ValueKey + ?Sized)) -> Option<Ref<TSvc>> {
+        let (provider, descriptor) = self.lookup(&Type::value_keyed::<TSvc>(key))?;
+        self.activate_optional(provider, &descriptor)
+    }
+
+    /// Gets a mutable service of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the service is keyed by
+    #[inline]
+    pub fn get_by_value_key_mut<TSvc: Any + ?Sized>(&self, key: &(impl ValueKey + ?Sized)) -> Option<RefMut<TSvc>> {
+        self.get_by_value_key::<Mut<TSvc>>(key)
+    }
+
+    /// Gets the runtime values that services of the specified type are keyed by.
+    ///
+    /// # Remarks
+    ///
+    /// Each key is returned once, in no particular order, as it is displayed.
+    pub fn get_all_value_keys<TSvc: Any + ?Sized>(&self) -> impl Iterator<Item = &str> + '_ {
+        let service_type = Type::of::<TSvc>();
+
+        let mut seen = HashSet::new();
+
+        self.lineage()
+            .flat_map(|provider| provider.services.keys())
+            .filter_map(move |key| {
+                if key.is_value_keyed() && key.is_same_type(&service_type) && seen.insert(key) {
+                    Type::deconstruct(key).1
+                } else {
+                    None
+                }
+            })
+    }
+
     /// Gets all of the services of the specified type.
     pub fn get_all<T: Any + ?Sized>(&self) -> impl Iterator<Item = Ref<T>> + '_ {
         let key = Type::of::<T>();
 
-        if let Some(descriptors) = self.services.get(&key) {
-            ServiceIterator::new(self, descriptors.iter())
-        } else {
-            ServiceIterator::new(self, empty())
-        }
+        ServiceIterator::new(self, self.lookup_all(&key))
     }
 
     /// Gets all of the mutable services of the specified type.
@@ -81,6 +299,33 @@ impl ServiceProvider {
         self.get_all::<Mut<T>>()
     }
 
+    /// Gets all of the services of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the services are keyed by
+    pub fn get_all_by_value_key<TSvc: Any + ?Sized>(
+        &self,
+        key: &(impl ValueKey + ?Sized),
+    ) -> impl Iterator<Item = Ref<TSvc>> + '_ {
+        let key = Type::value_keyed::<TSvc>(key);
+
+        ServiceIterator::new(self, self.lookup_all(&key))
+    }
+
+    /// Gets all of the mutable services of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the services are keyed by
+    #[inline]
+    pub fn get_all_by_value_key_mut<TSvc: Any + ?Sized>(
+        &self,
+        key: &(impl ValueKey + ?Sized),
+    ) -> impl Iterator<Item = RefMut<TSvc>> + '_ {
+        self.get_all_by_value_key::<Mut<TSvc>>(key)
+    }
+
     /// Gets all of the services of the specified key and type.
     pub fn get_all_by_key<'a, TKey: 'a, TSvc>(&'a self) -> impl Iterator<Item = KeyedRef<TKey, TSvc>> + 'a
     where
@@ -88,11 +333,7 @@ impl ServiceProvider {
     {
         let key = Type::keyed::<TKey, TSvc>();
 
-        if let Some(descriptors) = self.services.get(&key) {
-            KeyedServiceIterator::new(self, descriptors.iter())
-        } else {
-            KeyedServiceIterator::new(self, empty())
-        }
+        KeyedServiceIterator::new(self, self.lookup_all(&key))
     }
 
     /// Gets all of the mutable services of the specified key and type.
@@ -108,13 +349,10 @@ impl ServiceProvider {
     ///
     /// # Panics
     ///
-    /// The requested service of type `T` does not exist.
+    /// The requested service of type `T` does not exist or the service, directly or indirectly, requires itself
+    /// while it is being activated. The panic message contains the resolution path that led to the failure.
     pub fn get_required<T: Any + ?Sized>(&self) -> Ref<T> {
-        if let Some(service) = self.get::<T>() {
-            service
-        } else {
-            panic!("No service for type '{}' has been registered.", type_name::<T>());
-        }
+        expect(self.try_get_required::<T>())
     }
 
     /// Gets a required, mutable service of the specified type.
@@ -133,15 +371,7 @@ impl ServiceProvider {
     ///
     /// The requested service of type `TSvc` with key `TKey` does not exist.
     pub fn get_required_by_key<TKey, TSvc: Any + ?Sized>(&self) -> KeyedRef<TKey, TSvc> {
-        if let Some(service) = self.get_by_key::<TKey, TSvc>() {
-            service
-        } else {
-            panic!(
-                "No service for type '{}' with the key '{}' has been registered.",
-                type_name::<TSvc>(),
-                type_name::<TKey>()
-            );
-        }
+        expect(self.try_get_required_by_key::<TKey, TSvc>())
     }
 
     /// Gets a required keyed service of the specified type.
@@ -154,152 +384,794 @@ impl ServiceProvider {
         self.get_required_by_key::<TKey, Mut<TSvc>>()
     }
 
-    /// Creates and returns a new service provider that is used to resolve
-    /// services from a newly create scope.
-    #[inline]
-    pub fn create_scope(&self) -> Self {
-        Self::new(self.services.as_ref().clone())
+    /// Gets a required service of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the service is keyed by
+    ///
+    /// # Panics
+    ///
+    /// The requested service of type `TSvc` with the value `key` does not exist.
+    pub fn get_required_by_value_key<TSvc: Any + ?Sized>(&self, key: &(impl ValueKey + ?Sized)) -> Ref<TSvc> {
+        expect(self.try_get_required_by_value_key::<TSvc>(key))
     }
-}
 
-/// Represents a scoped [ServiceProvider].
-///
-/// # Remarks
-///
-/// This struct has the exact same functionality as [ServiceProvider]. When a new instance is created, it also creates
-/// a new scope from the source [ServiceProvider]. The primary use case for this struct is to explicitly declare that
-/// a new scope should be created at the injection call site.
-#[derive(Clone, Default)]
-pub struct ScopedServiceProvider {
-    sp: ServiceProvider,
-}
+    /// Gets a required, mutable service of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the service is keyed by
+    ///
+    /// # Panics
+    ///
+    /// The requested service of type `TSvc` with the value `key` does not exist.
+    #[inline]
+    pub fn get_required_by_value_key_mut<TSvc: Any + ?Sized>(&self, key: &(impl ValueKey + ?Sized)) -> RefMut<TSvc> {
+        self.get_required_by_value_key::<Mut<TSvc>>(key)
+    }
 
-impl From<&ServiceProvider> for ScopedServiceProvider {
-    fn from(value: &ServiceProvider) -> Self {
-        Self {
-            sp: value.create_scope(),
-        }
+    /// Attempts to get a required service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Any failure that occurs while the service or one of its dependencies is activated is returned as a
+    /// [ResolveError] instead of panicking. A dependency is only reported this way if the factory of the service
+    /// that depends on it resolves it fallibly, such as with this function in a
+    /// [fallible factory](crate::ServiceDescriptorBuilder::try_from). A service factory that panics is never caught.
+    pub fn try_get_required<T: Any + ?Sized>(&self) -> Result<Ref<T>, ResolveError> {
+        let key = Type::of::<T>();
+        self.locate(&key)?.ok_or_else(|| ResolveError::missing(key))
     }
-}
 
-impl AsRef<ServiceProvider> for ScopedServiceProvider {
+    /// Attempts to get a required, mutable service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [try_get_required](Self::try_get_required) for more information.
     #[inline]
-    fn as_ref(&self) -> &ServiceProvider {
-        &self.sp
+    pub fn try_get_required_mut<T: Any + ?Sized>(&self) -> Result<RefMut<T>, ResolveError> {
+        self.try_get_required::<Mut<T>>()
     }
-}
 
-impl Borrow<ServiceProvider> for ScopedServiceProvider {
+    /// Attempts to get a required keyed service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [try_get_required](Self::try_get_required) for more information.
+    pub fn try_get_required_by_key<TKey, TSvc: Any + ?Sized>(&self) -> Result<KeyedRef<TKey, TSvc>, ResolveError> {
+        let key = Type::keyed::<TKey, TSvc>();
+        self.locate(&key)?
+            .map(KeyedRef::new)
+            .ok_or_else(|| ResolveError::missing(key))
+    }
+
+    /// Attempts to get a required, keyed, mutable service of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [try_get_required](Self::try_get_required) for more information.
     #[inline]
-    fn borrow(&self) -> &ServiceProvider {
-        &self.sp
+    pub fn try_get_required_by_key_mut<TKey, TSvc: Any + ?Sized>(
+        &self,
+    ) -> Result<KeyedRefMut<TKey, TSvc>, ResolveError> {
+        self.try_get_required_by_key::<TKey, Mut<TSvc>>()
     }
-}
 
-impl Deref for ScopedServiceProvider {
-    type Target = ServiceProvider;
+    /// Attempts to get a required service of the specified type keyed by a runtime value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value the service is keyed by
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [try_get_required](Self::try_get_required) for more information.
+    pub fn try_get_required_by_value_key<TSvc: Any + ?Sized>(
+        &self,
+        key: &(impl ValueKey + ?Sized),
+    ) -> Result<Ref<TSvc>, ResolveError> {
+        let key = Type::value_keyed::<TSvc>(key);
+        self.locate(&key)?.ok_or_else(|| ResolveError::missing(key))
+    }
 
-    #[inline]
-    fn deref(&self) -> &Self::Target {
-        &self.sp
+    /// Attempts to get all of the services of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Unlike [get_all](Self::get_all), every service is activated before the result is returned. If any service fails
+    /// to be activated, the first failure is returned. Refer to [try_get_required](Self::try_get_required) for more
+    /// information.
+    pub fn try_get_all<T: Any + ?Sized>(&self) -> Result<Vec<Ref<T>>, ResolveError> {
+        self.lookup_all(&Type::of::<T>())
+            .map(|(provider, descriptor)| self.activate(provider, descriptor))
+            .collect()
     }
-}
 
-struct ServiceIterator<'a, T: Any + ?Sized> {
-    provider: &'a ServiceProvider,
-    descriptors: Box<dyn Iterator<Item = &'a ServiceDescriptor> + 'a>,
-    _marker: PhantomData<T>,
-}
+    /// Attempts to get all of the mutable services of the specified type.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [try_get_all](Self::try_get_all) for more information.
+    #[inline]
+    pub fn try_get_all_mut<T: Any + ?Sized>(&self) -> Result<Vec<RefMut<T>>, ResolveError> {
+        self.try_get_all::<Mut<T>>()
+    }
 
-struct KeyedServiceIterator<'a, TKey, TSvc: Any + ?Sized> {
-    provider: &'a ServiceProvider,
-    descriptors: Box<dyn Iterator<Item = &'a ServiceDescriptor> + 'a>,
-    _key: PhantomData<TKey>,
-    _svc: PhantomData<TSvc>,
-}
+    /// Creates and returns a new service provider that is used to resolve
+    /// services from a newly create scope.
+    ///
+    /// # Remarks
+    ///
+    /// A scope shares the registered services of the provider and only allocates storage for the instances of its
+    /// scoped services, which makes creating a scope inexpensive regardless of the number of registrations. The scope
+    /// of a [child](Self::create_child) provider also creates a new scope from its parent.
+    pub fn create_scope(&self) -> Self {
+        let disposables = Ref::new(Disposables::default());
 
-impl<'a, T: Any + ?Sized> ServiceIterator<'a, T> {
-    fn new<I>(provider: &'a ServiceProvider, descriptors: I) -> Self
-    where
-        I: Iterator<Item = &'a ServiceDescriptor> + 'a,
-    {
         Self {
-            provider,
-            descriptors: Box::new(descriptors),
-            _marker: PhantomData,
+            services: self.services.clone(),
+            instances: new_instances(self.instances.len()),
+            parent: self.parent.as_ref().map(|parent| Ref::new(parent.create_scope())),
+            strict: self.strict.clone(),
+            options: self.options.clone(),
+            validate_scopes: self.validate_scopes,
+            scoped: true,
+            singleton: None,
+            observer: self.observer.clone(),
+            disposables: disposables.clone(),
+            root: self.root.clone(),
+            owner: Some(Ref::new(Owner::new(disposables, self.owner.as_ref().map(Owner::root)))),
+            generics: Ref::new(ClosedGenerics::default()),
+            root_generics: self.root_generics.clone(),
+            closers: self.closers.clone(),
         }
     }
-}
 
-impl<'a, T: Any + ?Sized> Iterator for ServiceIterator<'a, T> {
-    type Item = Ref<T>;
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(descriptor) = self.descriptors.next() {
-            Some(descriptor.get(self.provider).downcast_ref::<Ref<T>>().unwrap().clone())
-        } else {
-            None
+    /// Creates and returns a new child service provider with additional services layered over the provider.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - The function used to register the services of the child provider
+    ///
+    /// # Remarks
+    ///
+    /// A child provider resolves the services it registers itself and falls back to the parent provider for any other
+    /// service. A service registered by the child overrides the service of the same type in the parent, while
+    /// [get_all](Self::get_all) returns the services of the parent followed by the services of the child. A service
+    /// that falls back to the parent is resolved by the parent so parent singletons are shared and the dependencies of
+    /// a parent service are never replaced by the child. The child owns the singletons it registers, which are disposed
+    /// with the child. The registrations of the child are [validated](crate::validate_with) against the combined
+    /// services of the child and all of its ancestors using the same [options](ValidationOptions) the provider was
+    /// built with, which also apply to the child itself, such as whether its singletons are activated when it is
+    /// created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use di::*;
+    ///
+    /// #[injectable]
+    /// struct Host;
+    ///
+    /// #[injectable]
+    /// struct Plugin {
+    ///     host: Ref<Host>,
+    /// }
+    ///
+    /// let app = ServiceCollection::new()
+    ///     .add(Host::singleton())
+    ///     .build_provider()
+    ///     .unwrap();
+    /// let plugin = app.create_child(|services| {
+    ///     services.add(Plugin::transient());
+    /// })
+    /// .unwrap();
+    ///
+    /// assert!(Ref::ptr_eq(&plugin.get_required::<Plugin>().host, &app.get_required::<Host>()));
+    /// assert!(app.get::<Plugin>().is_none());
+    /// ```
+    pub fn create_child(&self, configure: impl FnOnce(&mut ServiceCollection)) -> Result<Self, ValidationError> {
+        let mut services = ServiceCollection::new();
+
+        configure(&mut services);
+
+        let mut lineage: Vec<_> = self.lineage().collect();
+        let mut combined = ServiceCollection::new();
+
+        // the child is validated as if its services were registered after those of its ancestors
+        while let Some(provider) = lineage.pop() {
+            for descriptor in provider.services.values().flatten() {
+                combined.add(descriptor.clone());
+            }
         }
-    }
-}
 
-impl<'a, TKey, TSvc: Any + ?Sized> KeyedServiceIterator<'a, TKey, TSvc> {
-    fn new<I>(provider: &'a ServiceProvider, descriptors: I) -> Self
-    where
-        I: Iterator<Item = &'a ServiceDescriptor> + 'a,
-    {
-        Self {
-            provider,
-            descriptors: Box::new(descriptors),
-            _key: PhantomData,
-            _svc: PhantomData,
+        for descriptor in &services {
+            combined.add(descriptor.clone());
         }
-    }
-}
 
-impl<'a, TKey, TSvc: Any + ?Sized> Iterator for KeyedServiceIterator<'a, TKey, TSvc> {
-    type Item = KeyedRef<TKey, TSvc>;
+        validate_with(&combined, &self.options)?;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if let Some(descriptor) = self.descriptors.next() {
-            Some(KeyedRef::new(
-                descriptor
-                    .get(self.provider)
-                    .downcast_ref::<Ref<TSvc>>()
-                    .unwrap()
-                    .clone(),
-            ))
+        let options = self.options.clone();
+        let child = Self {
+            parent: Some(Ref::new(self.clone())),
+            observer: self.observer.clone(),
+            ..Self::with_options(services.build(options.is_strict()), options.clone())
+        };
+
+        eager::activate(&services, &child, options.activation(), options.is_parallel())?;
+        Ok(child)
+    }
+
+    /// Gets the services that were resolved during the activation of another service without being declared as one
+    /// of its dependencies.
+    ///
+    /// # Remarks
+    ///
+    /// Undeclared dependencies are only recorded by a provider created with
+    /// [build_strict_provider](ServiceCollection::build_strict_provider). Each undeclared dependency is reported once,
+    /// in the order it was first resolved, and the record is shared by every scope of the provider.
+    pub fn undeclared_dependencies(&self) -> Vec<UndeclaredDependency> {
+        if let Some(strict) = &self.strict {
+            strict.undeclared()
         } else {
-            None
+            Vec::new()
         }
     }
-}
 
-impl Default for ServiceProvider {
-    fn default() -> Self {
-        Self {
-            services: Ref::new(HashMap::new()),
+    /// Asserts that every service resolved during the activation of another service was declared as one of its
+    /// dependencies.
+    ///
+    /// # Panics
+    ///
+    /// One or more [undeclared dependencies](Self::undeclared_dependencies) have been recorded.
+    pub fn assert_dependencies_declared(&self) {
+        let undeclared = self.undeclared_dependencies();
+
+        if undeclared.is_empty() {
+            return;
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        existing, existing_as_self, scoped, singleton, singleton_as_self, singleton_with_key, test::*, transient, Ref,
-        ServiceCollection,
-    };
-    use std::fs::remove_file;
-    use std::path::{Path, PathBuf};
+        let mut message = String::from("One or more services resolved dependencies that have not been declared.");
 
-    cfg_if::cfg_if! {
-        if #[cfg(feature = "async")] {
-            use std::sync::{Arc, Mutex};
-            use std::thread;
+        for (i, item) in undeclared.iter().enumerate() {
+            message.push_str(&format!("\n  [{}] {}", i + 1, item));
         }
+
+        panic!("{}", message)
     }
 
+    /// Disposes all of the [disposable](crate::Disposable) services owned by the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Services are disposed in the reverse order in which they were created. A scope owns the scoped and transient
+    /// services it activated, while singletons are owned by the root provider. Disposing a scope never disposes the
+    /// singletons it resolved. Disposal happens automatically when the last clone of the provider that owns the scope
+    /// is dropped so calling this function is only necessary when disposal must occur at a deterministic point before
+    /// then. A clone retained by a service does not own the scope. A disposed service provider should not be used to
+    /// resolve any more services.
+    pub fn dispose(&self) {
+        self.disposables.dispose()
+    }
+
+    /// Asynchronously disposes all of the [disposable](crate::Disposable) and
+    /// [asynchronously disposable](crate::AsyncDisposable) services owned by the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Services are disposed in the reverse order in which they were created, which guarantees that a service is
+    /// disposed before any of its dependencies. Every owned service is disposed even if another service fails to be
+    /// disposed. The returned future does not depend on any particular asynchronous runtime.
     #[cfg(feature = "async")]
-    fn assert_send_and_sync<T: Send + Sync>(_: T) {}
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn dispose_async(&self) -> Result<(), crate::DisposeError> {
+        self.disposables.dispose_async().await
+    }
+
+    /// Starts all of the [hosted services](crate::HostedService) registered with the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Hosted services are resolved as though by [get_all](Self::get_all) and started in dependency order, which
+    /// guarantees that a hosted service is started after any of the hosted services it depends on. Every hosted
+    /// service is started even if another service fails to be resolved or started.
+    pub fn start_all(&self) -> Result<(), HostError> {
+        self.host(false)
+    }
+
+    /// Stops all of the [hosted services](crate::HostedService) registered with the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Hosted services are stopped in the reverse order in which they are [started](Self::start_all), which
+    /// guarantees that a hosted service is stopped before any of the hosted services it depends on. Every hosted
+    /// service is stopped even if another service fails to be resolved or stopped.
+    pub fn stop_all(&self) -> Result<(), HostError> {
+        self.host(true)
+    }
+
+    /// Asynchronously starts all of the [hosted](crate::HostedService) and
+    /// [asynchronously hosted](crate::AsyncHostedService) services registered with the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Services are started in dependency order. Services at the same depth of dependency are started in the order
+    /// they were registered, with hosted services ahead of asynchronously hosted services. Every service is started
+    /// even if another service fails to be resolved or started. The returned future does not depend on any particular
+    /// asynchronous runtime.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn start_all_async(&self) -> Result<(), HostError> {
+        self.host_async(false).await
+    }
+
+    /// Asynchronously stops all of the [hosted](crate::HostedService) and
+    /// [asynchronously hosted](crate::AsyncHostedService) services registered with the provider.
+    ///
+    /// # Remarks
+    ///
+    /// Services are stopped in the reverse order in which they are [started](Self::start_all_async). Every service is
+    /// stopped even if another service fails to be resolved or stopped. The returned future does not depend on any
+    /// particular asynchronous runtime.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub async fn stop_all_async(&self) -> Result<(), HostError> {
+        self.host_async(true).await
+    }
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async")] {
+            generic!(Any + Send + Sync);
+            observer!(Send + Sync + 'static);
+        } else {
+            generic!(Any);
+            observer!('static);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn instance(&self, slot: usize) -> Option<&Instance> {
+        self.instances.get(slot)
+    }
+
+    #[inline]
+    pub(crate) fn observer(&self) -> Option<&Observer> {
+        self.observer.as_deref()
+    }
+
+    #[inline]
+    pub(crate) fn validates_scopes(&self) -> bool {
+        self.validate_scopes
+    }
+
+    // the provider supplied to a factory shares the same scope, but does not own it; otherwise, a service that
+    // retains the provider would keep its own scope alive. a singleton that retains the provider also marks it so
+    // that it guards against any scoped service the singleton resolves, now or later
+    pub(crate) fn captured_by(&self, singleton: Option<&Type>) -> Self {
+        Self {
+            singleton: singleton.or(self.singleton.as_ref()).cloned(),
+            owner: None,
+            ..self.clone()
+        }
+    }
+
+    // a service that is not registered is not an error here. it is only an error
+    // when the service is required, which is decided by the caller
+    fn locate<T: Any + ?Sized>(&self, key: &Type) -> Result<Option<Ref<T>>, ResolveError> {
+        let Some((provider, descriptor)) = self.lookup(key) else {
+            return Ok(None);
+        };
+
+        self.activate(provider, &descriptor).map(Some)
+    }
+
+    // fallible counterparts of get, get_by_key, and get_all_by_key used by lazy, factory, and owned services
+    pub(crate) fn try_get<T: Any + ?Sized>(&self) -> Result<Option<Ref<T>>, ResolveError> {
+        self.locate(&Type::of::<T>())
+    }
+
+    pub(crate) fn try_get_by_key<TKey, TSvc: Any + ?Sized>(
+        &self,
+    ) -> Result<Option<KeyedRef<TKey, TSvc>>, ResolveError> {
+        Ok(self.locate(&Type::keyed::<TKey, TSvc>())?.map(KeyedRef::new))
+    }
+
+    pub(crate) fn try_get_all_by_key<TKey, TSvc: Any + ?Sized>(
+        &self,
+    ) -> Result<Vec<KeyedRef<TKey, TSvc>>, ResolveError> {
+        self.lookup_all(&Type::keyed::<TKey, TSvc>())
+            .map(|(provider, descriptor)| self.activate(provider, descriptor).map(KeyedRef::new))
+            .collect()
+    }
+
+    // a closed generic type that is not registered can only be closed when it has been declared as a dependency
+    fn lookup(&self, key: &Type) -> Option<(&Self, Found<'_>)> {
+        self.lookup_or_close(key, || {
+            let dependency = self.lineage().find_map(|provider| provider.closers.get(key))?;
+            Some((dependency.definition()?.clone(), dependency.activator()?.clone()))
+        })
+    }
+
+    fn lookup_or_close(
+        &self,
+        key: &Type,
+        closer: impl FnOnce() -> Option<(Type, Ref<Activate>)>,
+    ) -> Option<(&Self, Found<'_>)> {
+        self.observe(key);
+
+        if let Some((provider, descriptor)) = self.find(key) {
+            Some((provider, Found::Registered(descriptor)))
+        } else {
+            let (provider, descriptor) = self.close(key, closer)?;
+            Some((provider, Found::Closed(descriptor)))
+        }
+    }
+
+    // the provider that registers the open generic type definition is the one that closes it. the closed
+    // type carries the dependencies declared by the open generic registration
+    fn close(
+        &self,
+        service_type: &Type,
+        closer: impl FnOnce() -> Option<(Type, Ref<Activate>)>,
+    ) -> Option<(&Self, Ref<ServiceDescriptor>)> {
+        let (definition, activate) = closer()?;
+        let (provider, open) = self.find(&definition)?;
+        let lifetime = open.lifetime();
+        let generics = if lifetime == ServiceLifetime::Singleton {
+            &provider.root_generics
+        } else {
+            &provider.generics
+        };
+        let mut generics = generics.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(descriptor) = generics.get(service_type) {
+            return Some((provider, descriptor.clone()));
+        }
+
+        let descriptor = Ref::new(ServiceDescriptor::new(
+            lifetime,
+            service_type.clone(),
+            service_type.clone(),
+            open.dependencies().to_vec(),
+            OnceLock::new(),
+            activate,
+        ));
+
+        if let Some(strict) = &self.strict {
+            strict.declare(&descriptor);
+        }
+
+        generics.insert(service_type.clone(), descriptor.clone());
+        Some((provider, descriptor))
+    }
+
+    fn lookup_all(&self, key: &Type) -> Descriptors<'_> {
+        self.observe(key);
+        self.find_all(key)
+    }
+
+    // the requesting provider guards the service, while the provider that owns it activates it
+    fn activate<T: Any + ?Sized>(
+        &self,
+        provider: &Self,
+        descriptor: &ServiceDescriptor,
+    ) -> Result<Ref<T>, ResolveError> {
+        self.guard(descriptor)?;
+        provider.resolve(descriptor)
+    }
+
+    // an optional service is only optional when it is not registered. a registered service that cannot be
+    // resolved from the requesting provider fails the same way it does for every other function
+    fn activate_optional<T: Any + ?Sized>(&self, provider: &Self, descriptor: &ServiceDescriptor) -> Option<Ref<T>> {
+        Some(expect(self.activate(provider, descriptor)))
+    }
+
+    // a scoped service resolved for a singleton or from the root would outlive
+    // its scope. a singleton is always checked first because it is more specific
+    fn guard(&self, descriptor: &ServiceDescriptor) -> Result<(), ResolveError> {
+        if descriptor.lifetime() != ServiceLifetime::Scoped {
+            return Ok(());
+        }
+
+        if let Some(singleton) = &self.singleton {
+            Err(ResolveError::captive(
+                descriptor.service_type().clone(),
+                singleton.clone(),
+            ))
+        } else if self.validate_scopes && !self.scoped {
+            Err(ResolveError::unscoped(descriptor.service_type().clone()))
+        } else {
+            Ok(())
+        }
+    }
+
+    #[inline]
+    fn observe(&self, key: &Type) {
+        if let Some(strict) = &self.strict {
+            strict.observe(key)
+        }
+    }
+
+    // a child provider falls back to its parent for any service it does not register
+    fn find(&self, key: &Type) -> Option<(&Self, &ServiceDescriptor)> {
+        if let Some(descriptor) = self.services.get(key).and_then(|descriptors| descriptors.last()) {
+            Some((self, descriptor))
+        } else {
+            self.parent.as_ref()?.find(key)
+        }
+    }
+
+    fn find_all(&self, key: &Type) -> Descriptors<'_> {
+        let descriptors = self
+            .services
+            .get(key)
+            .into_iter()
+            .flatten()
+            .map(move |descriptor| (self, descriptor));
+
+        if let Some(parent) = &self.parent {
+            Box::new(parent.find_all(key).chain(descriptors))
+        } else {
+            Box::new(descriptors)
+        }
+    }
+
+    fn lineage(&self) -> impl Iterator<Item = &Self> {
+        successors(Some(self), |provider| provider.parent.as_deref())
+    }
+
+    fn resolve<T: Any + ?Sized>(&self, descriptor: &ServiceDescriptor) -> Result<Ref<T>, ResolveError> {
+        if let Some(service) = descriptor.try_get(self)?.downcast_ref::<Ref<T>>() {
+            Ok(service.clone())
+        } else {
+            Err(ResolveError::type_mismatch(descriptor.service_type().clone()))
+        }
+    }
+
+    fn host(&self, stopping: bool) -> Result<(), HostError> {
+        let key = Type::of::<dyn HostedService>();
+        let mut services = self.hosted(self.find_hosted(&key).map(|item| (item, item.1)));
+        let mut errors = Vec::new();
+
+        if stopping {
+            services.reverse();
+        }
+
+        for (provider, descriptor) in services {
+            let result = self
+                .resolve_hosted::<dyn HostedService>(provider, descriptor)
+                .and_then(|service| if stopping { service.stop() } else { service.start() });
+
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        hosted::complete(stopping, errors)
+    }
+
+    #[cfg(feature = "async")]
+    async fn host_async(&self, stopping: bool) -> Result<(), HostError> {
+        let sync = Type::of::<dyn HostedService>();
+        let r#async = Type::of::<dyn crate::AsyncHostedService>();
+        let mut services = self.hosted(
+            self.find_hosted(&sync)
+                .map(|item| ((false, item), item.1))
+                .chain(self.find_hosted(&r#async).map(|item| ((true, item), item.1))),
+        );
+        let mut errors = Vec::new();
+
+        if stopping {
+            services.reverse();
+        }
+
+        for (is_async, (provider, descriptor)) in services {
+            let result = if is_async {
+                match self.resolve_hosted::<dyn crate::AsyncHostedService>(provider, descriptor) {
+                    Ok(service) if stopping => service.stop_async().await,
+                    Ok(service) => service.start_async().await,
+                    Err(error) => Err(error),
+                }
+            } else {
+                self.resolve_hosted::<dyn HostedService>(provider, descriptor)
+                    .and_then(|service| if stopping { service.stop() } else { service.start() })
+            };
+
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        hosted::complete(stopping, errors)
+    }
+
+    fn find_hosted(&self, key: &Type) -> Descriptors<'_> {
+        self.observe(key);
+        self.find_all(key)
+    }
+
+    // hosted services depend on each other through any registration in the lineage
+    fn hosted<'a, T>(&'a self, services: impl Iterator<Item = (T, &'a ServiceDescriptor)>) -> Vec<T> {
+        hosted::order(services.collect(), |key| {
+            self.find_all(key).map(|(_, descriptor)| descriptor)
+        })
+    }
+
+    // a hosted service that cannot be resolved is reported like any other failure
+    fn resolve_hosted<T: Any + ?Sized>(
+        &self,
+        provider: &Self,
+        descriptor: &ServiceDescriptor,
+    ) -> Result<Ref<T>, Box<dyn Error + Send + Sync>> {
+        Ok(self.activate(provider, descriptor)?)
+    }
+
+    // a registration is activated through the same lookup and guard as any other resolution. the
+    // registration of the provider is a clone of the one in the collection with the same identity
+    pub(crate) fn activate_registration(&self, registration: &ServiceDescriptor) -> Result<(), ResolveError> {
+        let key = registration.service_type();
+
+        self.observe(key);
+
+        if let Some((provider, descriptor)) = self
+            .find_all(key)
+            .find(|(_, descriptor)| descriptor.id() == registration.id())
+        {
+            self.guard(descriptor)?;
+            descriptor.try_get(provider)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn track(&self, lifetime: ServiceLifetime, item: Tracked) {
+        if lifetime == ServiceLifetime::Singleton {
+            self.root.track(item)
+        } else {
+            self.disposables.track(item)
+        }
+    }
+}
+
+/// Represents a scoped [ServiceProvider].
+///
+/// # Remarks
+///
+/// This struct has the exact same functionality as [ServiceProvider]. When a new instance is created, it also creates
+/// a new scope from the source [ServiceProvider]. The primary use case for this struct is to explicitly declare that
+/// a new scope should be created at the injection call site.
+#[derive(Clone, Default)]
+pub struct ScopedServiceProvider {
+    sp: ServiceProvider,
+}
+
+impl From<&ServiceProvider> for ScopedServiceProvider {
+    fn from(value: &ServiceProvider) -> Self {
+        Self {
+            sp: value.create_scope(),
+        }
+    }
+}
+
+impl AsRef<ServiceProvider> for ScopedServiceProvider {
+    #[inline]
+    fn as_ref(&self) -> &ServiceProvider {
+        &self.sp
+    }
+}
+
+impl Borrow<ServiceProvider> for ScopedServiceProvider {
+    #[inline]
+    fn borrow(&self) -> &ServiceProvider {
+        &self.sp
+    }
+}
+
+impl Deref for ScopedServiceProvider {
+    type Target = ServiceProvider;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.sp
+    }
+}
+
+// every scope shares the registered services and only owns the instances of its scoped services
+fn new_instances(slots: usize) -> Ref<[Instance]> {
+    (0..slots).map(|_| Instance::new()).collect()
+}
+
+type Descriptors<'a> = Box<dyn Iterator<Item = (&'a ServiceProvider, &'a ServiceDescriptor)> + 'a>;
+
+struct ServiceIterator<'a, T: Any + ?Sized> {
+    services: &'a ServiceProvider,
+    descriptors: Descriptors<'a>,
+    _marker: PhantomData<T>,
+}
+
+struct KeyedServiceIterator<'a, TKey, TSvc: Any + ?Sized> {
+    services: &'a ServiceProvider,
+    descriptors: Descriptors<'a>,
+    _key: PhantomData<TKey>,
+    _svc: PhantomData<TSvc>,
+}
+
+impl<'a, T: Any + ?Sized> ServiceIterator<'a, T> {
+    fn new(services: &'a ServiceProvider, descriptors: Descriptors<'a>) -> Self {
+        Self {
+            services,
+            descriptors,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Any + ?Sized> Iterator for ServiceIterator<'a, T> {
+    type Item = Ref<T>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((provider, descriptor)) = self.descriptors.next() {
+            Some(expect(self.services.activate(provider, descriptor)))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, TKey, TSvc: Any + ?Sized> KeyedServiceIterator<'a, TKey, TSvc> {
+    fn new(services: &'a ServiceProvider, descriptors: Descriptors<'a>) -> Self {
+        Self {
+            services,
+            descriptors,
+            _key: PhantomData,
+            _svc: PhantomData,
+        }
+    }
+}
+
+impl<'a, TKey, TSvc: Any + ?Sized> Iterator for KeyedServiceIterator<'a, TKey, TSvc> {
+    type Item = KeyedRef<TKey, TSvc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some((provider, descriptor)) = self.descriptors.next() {
+            Some(KeyedRef::new(expect(self.services.activate(provider, descriptor))))
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for ServiceProvider {
+    fn default() -> Self {
+        Self::new(HashMap::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::description::Activate;
+    use crate::{
+        exactly_one, exactly_one_generic, exactly_one_with_key, existing, existing_as_self, scoped, scoped_generic,
+        scoped_with_key, singleton, singleton_as_self, singleton_factory, singleton_generic, singleton_with_key,
+        singleton_with_key_factory, singleton_with_value_key, test::*, transient, transient_as_self, transient_generic,
+        transient_with_value_key, HostedService, OpenGeneric, Ref, ResolveError, ServiceCollection, ServiceDependency,
+        ServiceDescriptor, ServiceLifetime, ServiceProvider, Type, ValidationOptions,
+    };
+    use std::collections::HashMap;
+    use std::fs::remove_file;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, OnceLock};
+
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async")] {
+            use crate::AsyncHostedService;
+            use std::sync::Arc;
+            use std::thread;
+        }
+    }
+
+    #[cfg(feature = "async")]
+    fn assert_send_and_sync<T: Send + Sync>(_: T) {}
 
     #[test]
     fn get_should_return_none_when_service_is_unregistered() {
@@ -344,8 +1216,8 @@ mod tests {
     fn get_by_key_should_return_registered_service() {
         // arrange
         let services = ServiceCollection::new()
-            .add(singleton_with_key::<key::Thingy, dyn Thing, Thing1>().from(|_| Ref::new(Thing1::default())))
-            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1::default())))
+            .add(singleton_with_key::<key::Thingy, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
             .build_provider()
             .unwrap();
 
@@ -372,352 +1244,1915 @@ mod tests {
     }
 
     #[test]
-    fn get_required_by_key_should_return_registered_service() {
+    fn get_required_by_key_should_return_registered_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_with_key::<key::Thingy, dyn Thing, Thing3>().from(|_| Ref::new(Thing3)))
+            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let thing = services.get_required_by_key::<key::Thingy, dyn Thing>();
+
+        // assert
+        assert_eq!(&thing.to_string(), "di::test::Thing3");
+    }
+
+    #[test]
+    #[should_panic(expected = "No service for type 'dyn di::test::TestService' has been registered.")]
+    fn get_required_should_panic_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let _ = services.get_required::<dyn TestService>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "No service for type 'dyn di::test::Thing' with the key 'di::test::key::Thing1' has been registered."
+    )]
+    fn get_required_by_key_should_panic_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let _ = services.get_required_by_key::<key::Thing1, dyn Thing>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let result = services.try_get_required::<dyn TestService>();
+
+        // assert
+        match result {
+            Err(ResolveError::Missing { service, path }) => {
+                assert_eq!(service, Type::of::<dyn TestService>());
+                assert_eq!(path, vec![Type::of::<dyn TestService>()]);
+            }
+            _ => panic!("expected a missing service error"),
+        }
+    }
+
+    #[test]
+    fn try_get_required_by_key_should_return_error_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let result = services.try_get_required_by_key::<key::Thing1, dyn Thing>();
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "No service for type 'dyn di::test::Thing' with the key 'di::test::key::Thing1' has been registered."
+        );
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_with_path_when_dependency_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn OtherTestService, TestOptionalDepImpl>().try_from(|sp| {
+                Ok::<_, ResolveError>(Ref::new(TestOptionalDepImpl::new(Some(
+                    sp.try_get_required::<dyn TestService>()?,
+                ))))
+            }))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let error = services.try_get_required::<dyn OtherTestService>().err().unwrap();
+
+        // assert
+        assert!(matches!(error, ResolveError::Missing { .. }));
+        assert_eq!(error.service(), &Type::of::<dyn TestService>());
+        assert_eq!(
+            error.path(),
+            &[Type::of::<dyn OtherTestService>(), Type::of::<dyn TestService>()]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "A circular dependency was detected for service 'dyn di::test::TestService'.\n  \
+                               Resolution path: dyn di::test::TestService -> dyn di::test::OtherTestService -> \
+                               dyn di::test::TestService"
+    )]
+    fn get_required_should_panic_when_undeclared_dependency_is_circular() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|sp| {
+                let _ = sp.get_required::<dyn OtherTestService>();
+                Ref::new(TestServiceImpl::default())
+            }))
+            .add(
+                scoped::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = services.create_scope().get_required::<dyn TestService>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_when_activation_is_circular() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestCircularDepImpl>().try_from(|sp| {
+                Ok::<_, ResolveError>(Ref::new(TestCircularDepImpl::new(
+                    sp.try_get_required::<dyn TestService>()?,
+                )))
+            }))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let error = services.try_get_required::<dyn TestService>().err().unwrap();
+
+        // assert
+        assert!(matches!(error, ResolveError::Circular { .. }));
+        assert_eq!(
+            error.path(),
+            &[Type::of::<dyn TestService>(), Type::of::<dyn TestService>()]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Connection refused.")]
+    fn try_get_required_should_not_catch_panic_from_factory() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| panic!("Connection refused.")))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let _ = services.try_get_required::<dyn TestService>();
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_when_factory_fails() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().try_from(|_| Err("Connection refused.")))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let error = services.try_get_required::<dyn TestService>().err().unwrap();
+
+        // assert
+        match error {
+            ResolveError::Factory { service, error, .. } => {
+                assert_eq!(service, Type::of::<dyn TestService>());
+                assert_eq!(&error.to_string(), "Connection refused.");
+            }
+            _ => panic!("expected a factory error"),
+        }
+    }
+
+    #[test]
+    fn try_get_required_should_return_service_from_fallible_factory() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestServiceImpl>()
+                    .try_from(|_| Ok::<_, String>(Ref::new(TestServiceImpl::default()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = services.try_get_required::<dyn TestService>();
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_when_fallible_factory_fails() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn OtherTestService, TestOptionalDepImpl>().try_from(|sp| {
+                Ok::<_, ResolveError>(Ref::new(TestOptionalDepImpl::new(Some(
+                    sp.try_get_required::<dyn TestService>()?,
+                ))))
+            }))
+            .add(singleton::<dyn TestService, TestServiceImpl>().try_from(|_| Err("Invalid configuration.")))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let error = services.try_get_required::<dyn OtherTestService>().err().unwrap();
+
+        // assert
+        assert_eq!(
+            &error.to_string(),
+            "The factory for service 'dyn di::test::TestService' failed: Invalid configuration.\n  \
+             Resolution path: dyn di::test::OtherTestService -> dyn di::test::TestService"
+        );
+    }
+
+    #[test]
+    fn try_get_required_should_return_error_when_service_type_mismatches() {
+        // arrange
+        let mut services = HashMap::new();
+        let factory: Ref<Activate> = Ref::new(|_| Ok(Ref::new(Ref::new(42usize))));
+
+        services.insert(
+            Type::of::<dyn TestService>(),
+            vec![ServiceDescriptor::new(
+                ServiceLifetime::Transient,
+                Type::of::<dyn TestService>(),
+                Type::of::<TestServiceImpl>(),
+                Vec::new(),
+                OnceLock::new(),
+                factory,
+            )],
+        );
+
+        let provider = ServiceProvider::new(services);
+
+        // act
+        let result = provider.try_get_required::<dyn TestService>();
+
+        // assert
+        assert!(matches!(result, Err(ResolveError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn try_get_required_should_recover_after_failure() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let _ = services.try_get_required::<dyn OtherTestService>();
+
+        // act
+        let result = services.try_get_required::<dyn TestService>();
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn try_get_all_should_return_all_services() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(singleton::<dyn Thing, Thing2>().from(|_| Ref::new(Thing2)))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = services.try_get_all::<dyn Thing>().unwrap();
+
+        // assert
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn try_get_all_should_return_error_when_any_service_fails() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(transient::<dyn Thing, Thing2>().try_from(|_| Err("Thing2 is broken.")))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = services.try_get_all::<dyn Thing>();
+
+        // assert
+        assert!(matches!(result, Err(ResolveError::Factory { .. })));
+    }
+
+    #[test]
+    fn get_by_value_key_should_return_service_for_key() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_with_value_key::<dyn Thing, Thing1>("tenant-1").from(|_| Ref::new(Thing1)))
+            .add(singleton_with_value_key::<dyn Thing, Thing2>("tenant-2").from(|_| Ref::new(Thing2)))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let thing1 = services.get_by_value_key::<dyn Thing>("tenant-1").unwrap();
+        let thing2 = services
+            .get_by_value_key::<dyn Thing>(&String::from("tenant-2"))
+            .unwrap();
+
+        // assert
+        assert_eq!(&thing1.to_string(), "di::test::Thing1");
+        assert_eq!(&thing2.to_string(), "di::test::Thing2");
+    }
+
+    #[test]
+    fn get_by_value_key_should_return_none_for_unregistered_key() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_with_value_key::<dyn Thing, Thing1>(1).from(|_| Ref::new(Thing1)))
+            .add(singleton_with_key::<key::Thing1, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = services.get_by_value_key::<dyn Thing>(&2);
+
+        // assert
+        assert!(result.is_none());
+        assert!(services.get::<dyn Thing>().is_none());
+    }
+
+    #[test]
+    fn get_all_value_keys_should_return_keys_for_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_with_value_key::<dyn Thing, Thing1>("primary").from(|_| Ref::new(Thing1)))
+            .add(transient_with_value_key::<dyn Thing, Thing2>("replica").from(|_| Ref::new(Thing2)))
+            .add(transient_with_value_key::<dyn Thing, Thing3>("replica").from(|_| Ref::new(Thing3)))
+            .add(singleton_with_key::<key::Thing1, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let mut keys: Vec<_> = services.get_all_value_keys::<dyn Thing>().collect();
+
+        // assert
+        keys.sort();
+        assert_eq!(keys, ["primary", "replica"]);
+        assert_eq!(services.get_all_by_value_key::<dyn Thing>("replica").count(), 2);
+    }
+
+    #[test]
+    fn get_all_value_keys_should_not_return_keys_for_other_type_with_same_name() {
+        // arrange
+        fn register<T: Copy + Send + Sync + 'static>(value: T, key: &str) -> ServiceDescriptor {
+            singleton_with_value_key::<T, T>(key.to_owned()).from(move |_| Ref::new(value))
+        }
+
+        fn name<T>(_: &T) -> &'static str {
+            std::any::type_name::<T>()
+        }
+
+        fn keys<T: 'static>(_: T, services: &ServiceProvider) -> Vec<&str> {
+            services.get_all_value_keys::<T>().collect()
+        }
+
+        // every closure has a distinct type, but closures declared in the same function have the same name
+        let primary = || ();
+        let other = || ();
+
+        assert_eq!(name(&primary), name(&other));
+
+        let services = ServiceCollection::new()
+            .add(register(primary, "primary"))
+            .add(register(other, "other"))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let keys = keys(primary, &services);
+
+        // assert
+        assert_eq!(keys, ["primary"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "No service for type 'dyn di::test::Thing' with the key 'primary' has been registered.")]
+    fn get_required_by_value_key_should_panic_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let _ = services.get_required_by_value_key::<dyn Thing>("primary");
+
+        // assert
+        // panics
+    }
+
+    #[test]
+    fn get_generic_should_return_none_when_service_is_unregistered() {
+        // arrange
+        let services = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let result = services.get_generic::<Repository<User>>();
+
+        // assert
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn get_generic_should_activate_any_closed_type_of_open_generic() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient_generic::<Repository<()>>())
+            .build_provider()
+            .unwrap();
+
+        // act
+        let users = services.get_generic::<Repository<User>>();
+        let orders = services.get_generic::<Repository<Order>>();
+
+        // assert
+        assert!(users.is_some());
+        assert!(orders.is_some());
+    }
+
+    #[test]
+    fn get_generic_should_prefer_closed_registration() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_generic::<Repository<()>>())
+            .add(existing_as_self(Repository::<User>::default()))
+            .build_provider()
+            .unwrap();
+        let expected = services.get_required::<Repository<User>>();
+
+        // act
+        let users = services.get_required_generic::<Repository<User>>();
+
+        // assert
+        assert!(Ref::ptr_eq(&users, &expected));
+    }
+
+    #[test]
+    fn get_generic_should_return_same_instance_for_singleton_across_scopes() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton_generic::<Repository<()>>())
+            .build_provider()
+            .unwrap();
+        let scope = services.create_scope();
+
+        // act
+        let users1 = services.get_required_generic::<Repository<User>>();
+        let users2 = scope.get_required_generic::<Repository<User>>();
+
+        // assert
+        assert!(Ref::ptr_eq(&users1, &users2));
+    }
+
+    #[test]
+    fn get_generic_should_return_instance_per_scope_for_scoped_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped_generic::<Repository<()>>())
+            .build_provider()
+            .unwrap();
+        let scope = services.create_scope();
+
+        // act
+        let users1 = scope.get_required_generic::<Repository<User>>();
+        let users2 = scope.get_required_generic::<Repository<User>>();
+        let users3 = services.create_scope().get_required_generic::<Repository<User>>();
+
+        // assert
+        assert!(Ref::ptr_eq(&users1, &users2));
+        assert!(!Ref::ptr_eq(&users1, &users3));
+    }
+
+    #[test]
+    fn get_generic_should_inject_closed_type_into_dependent_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped_generic::<Repository<()>>())
+            .add(
+                transient_as_self::<UserService>()
+                    .depends_on(exactly_one_generic::<Repository<User>>())
+                    .from(|sp| {
+                        Ref::new(UserService {
+                            users: sp.get_required_generic::<Repository<User>>(),
+                        })
+                    }),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = services.create_scope();
+
+        // act
+        let service = scope.get_required::<UserService>();
+
+        // assert
+        assert!(Ref::ptr_eq(
+            &service.users,
+            &scope.get_required_generic::<Repository<User>>()
+        ));
+    }
+
+    #[test]
+    fn get_required_should_activate_closed_type_of_generic_dependency() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped_generic::<Repository<()>>())
+            .add(
+                transient_as_self::<UserService>()
+                    .depends_on(exactly_one_generic::<Repository<User>>())
+                    .from(|sp| {
+                        Ref::new(UserService {
+                            users: sp.get_required::<Repository<User>>(),
+                        })
+                    }),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = services.create_scope();
+
+        // act
+        let service = scope.get_required::<UserService>();
+
+        // assert
+        assert!(Ref::ptr_eq(
+            &service.users,
+            &scope.get_required_generic::<Repository<User>>()
+        ));
+    }
+
+    #[test]
+    fn get_should_return_none_for_closed_type_that_is_not_declared_as_generic_dependency() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient_generic::<Repository<()>>())
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = services.get::<Repository<User>>();
+
+        // assert
+        assert!(result.is_none());
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn get_should_return_same_instance_for_singleton_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(existing::<dyn TestService, TestServiceImpl>(Box::new(
+                TestServiceImpl::default(),
+            )))
+            .add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let svc2 = services.get_required::<dyn OtherTestService>();
+        let svc1 = services.get_required::<dyn OtherTestService>();
+
+        // assert
+        assert!(Ref::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn get_should_return_different_instances_for_transient_service() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let svc1 = services.get_required::<dyn TestService>();
+        let svc2 = services.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!Ref::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    fn get_all_should_return_all_services() {
+        // arrange
+        let mut collection = ServiceCollection::new();
+
+        collection
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl { value: 1 })))
+            .add(singleton::<dyn TestService, TestService2Impl>().from(|_| Ref::new(TestService2Impl { value: 2 })));
+
+        let provider = collection.build_provider().unwrap();
+
+        // act
+        let services = provider.get_all::<dyn TestService>();
+        let values: Vec<_> = services.map(|s| s.value()).collect();
+
+        // assert
+        assert_eq!(&values, &[1, 2]);
+    }
+
+    #[test]
+    fn get_all_by_key_should_return_all_services() {
+        // arrange
+        let mut collection = ServiceCollection::new();
+
+        collection
+            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing2>().from(|_| Ref::new(Thing2)))
+            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing3>().from(|_| Ref::new(Thing3)));
+
+        let provider = collection.build_provider().unwrap();
+
+        // act
+        let services = provider.get_all_by_key::<key::Thingies, dyn Thing>();
+        let values: Vec<_> = services.map(|s| s.to_string()).collect();
+
+        // assert
+        assert_eq!(
+            &values,
+            &[
+                "di::test::Thing1".to_owned(),
+                "di::test::Thing2".to_owned(),
+                "di::test::Thing3".to_owned()
+            ]
+        );
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn two_scoped_service_providers_should_create_different_instances() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = services.create_scope();
+
+        // act
+        let svc1 = scope1.get_required::<dyn TestService>();
+        let svc2 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!Ref::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn parent_child_scoped_service_providers_should_create_different_instances() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+
+        // act
+        let svc1 = scope1.get_required::<dyn TestService>();
+        let svc2 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(!Ref::ptr_eq(&svc1, &svc2));
+    }
+
+    #[test]
+    fn create_scope_should_share_registered_services() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                scoped::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let scope = services.create_scope();
+
+        // assert
+        assert!(Ref::ptr_eq(&services.services, &scope.services));
+        assert_eq!(scope.instances.len(), 1);
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn scoped_service_provider_should_have_same_singleton_when_eager_created_in_parent() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let svc1 = services.get_required::<dyn TestService>();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+
+        // act
+        let svc2 = scope1.get_required::<dyn TestService>();
+        let svc3 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(Ref::ptr_eq(&svc1, &svc2));
+        assert!(Ref::ptr_eq(&svc1, &svc3));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn scoped_service_provider_should_have_same_singleton_when_lazy_created_in_parent() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let scope1 = services.create_scope();
+        let scope2 = scope1.create_scope();
+        let svc1 = services.get_required::<dyn TestService>();
+
+        // act
+        let svc2 = scope1.get_required::<dyn TestService>();
+        let svc3 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(Ref::ptr_eq(&svc1, &svc2));
+        assert!(Ref::ptr_eq(&svc1, &svc3));
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn create_child_should_fall_back_to_parent_singleton() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                        .depends_on(exactly_one::<dyn TestService>())
+                        .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+                );
+            })
+            .unwrap();
+
+        // act
+        let svc1 = child.get_required::<dyn TestService>();
+        let svc2 = parent.get_required::<dyn TestService>();
+
+        // assert
+        assert!(Ref::ptr_eq(&svc1, &svc2));
+        assert!(child.get::<dyn OtherTestService>().is_some());
+        assert!(parent.get::<dyn OtherTestService>().is_none());
+    }
+
+    #[test]
+    fn create_child_should_override_parent_service() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl { value: 1 })))
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services.add(
+                    transient::<dyn TestService, TestService2Impl>().from(|_| Ref::new(TestService2Impl { value: 2 })),
+                );
+            })
+            .unwrap();
+
+        // act
+        let values: Vec<_> = child.get_all::<dyn TestService>().map(|svc| svc.value()).collect();
+
+        // assert
+        assert_eq!(child.get_required::<dyn TestService>().value(), 2);
+        assert_eq!(parent.get_required::<dyn TestService>().value(), 1);
+        assert_eq!(values, [1, 2]);
+    }
+
+    #[test]
+    fn create_child_should_validate_against_parent_services() {
+        // arrange
+        let parent = ServiceCollection::new().build_provider().unwrap();
+
+        // act
+        let result = parent.create_child(|services| {
+            services.add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            );
+        });
+
+        // assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn create_child_should_validate_with_options_of_parent() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .build_provider_with(ValidationOptions::default().remove_rule("MissingRequiredType"))
+            .unwrap();
+
+        // act
+        let result = parent.create_child(|services| {
+            services.add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            );
+        });
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn create_child_should_activate_singletons_when_parent_activates_singletons() {
+        // arrange
+        let log = ActivationLog::default();
+        let parent = ServiceCollection::new()
+            .build_provider_with(ValidationOptions::default().activate_singletons(true))
+            .unwrap();
+        let log1 = log.clone();
+
+        // act
+        let _child = parent
+            .create_child(|services| {
+                services.add(singleton_as_self::<Thing1>().from(move |_| {
+                    log1.activated("thing");
+                    Ref::new(Thing1)
+                }));
+            })
+            .unwrap();
+
+        // assert
+        assert_eq!(log.order(), ["thing"]);
+    }
+
+    #[test]
+    fn create_child_should_report_singleton_that_depends_on_scoped_parent_service() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = parent.create_child(|services| {
+            services.add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            );
+        });
+
+        // assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn create_scope_should_create_scope_of_parent_for_child() {
+        // arrange
+        let parent = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+        let child = parent.create_child(|_| {}).unwrap();
+        let scope1 = child.create_scope();
+        let scope2 = child.create_scope();
+
+        // act
+        let svc1 = scope1.get_required::<dyn TestService>();
+        let svc2 = scope1.get_required::<dyn TestService>();
+        let svc3 = scope2.get_required::<dyn TestService>();
+
+        // assert
+        assert!(Ref::ptr_eq(&svc1, &svc2));
+        assert!(!Ref::ptr_eq(&svc1, &svc3));
+        assert!(!Ref::ptr_eq(
+            &svc1,
+            &parent.create_scope().get_required::<dyn TestService>()
+        ));
+    }
+
+    #[test]
+    fn drop_should_dispose_singleton_owned_by_child() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let log2 = log.clone();
+        let parent = ServiceCollection::new()
+            .add(
+                singleton::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("parent", log1.clone()))),
+            )
+            .build_provider()
+            .unwrap();
+        let child = parent
+            .create_child(move |services| {
+                services.add(
+                    singleton_with_value_key::<Disposer, Disposer>("child")
+                        .disposable()
+                        .from(move |_| Ref::new(Disposer::new("child", log2.clone()))),
+                );
+            })
+            .unwrap();
+
+        // act
+        let _ = child.get_required::<Disposer>();
+        let _ = child.get_required_by_value_key::<Disposer>("child");
+        drop(child);
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["child"]);
+    }
+
+    #[test]
+    fn strict_provider_should_record_undeclared_dependency() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_strict_provider()
+            .unwrap();
+
+        // act
+        let _ = services.get_required::<dyn OtherTestService>();
+        let _ = services.get_required::<dyn OtherTestService>();
+
+        // assert
+        let undeclared = services.undeclared_dependencies();
+        assert_eq!(undeclared.len(), 1);
+        assert_eq!(undeclared[0].service_type(), &Type::of::<dyn OtherTestService>());
+        assert_eq!(undeclared[0].dependency_type(), &Type::of::<dyn TestService>());
+    }
+
+    #[test]
+    fn strict_provider_should_not_record_declared_dependency() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_strict_provider()
+            .unwrap();
+        let scope = services.create_scope();
+
+        // act
+        let _ = scope.get_required::<dyn OtherTestService>();
+        let _ = scope.get::<dyn AnotherTestService>();
+
+        // assert
+        services.assert_dependencies_declared();
+    }
+
+    #[test]
+    fn strict_provider_should_not_record_dependency_declared_by_open_generic() {
+        // arrange
+        struct Cache<T>(std::marker::PhantomData<fn() -> T>);
+
+        impl<T> OpenGeneric for Cache<T> {
+            type Definition = Cache<()>;
+
+            fn dependencies() -> Vec<ServiceDependency> {
+                vec![exactly_one::<dyn TestService>()]
+            }
+
+            fn activate(services: &ServiceProvider) -> Ref<Self> {
+                let _ = services.get_required::<dyn TestService>();
+                Ref::new(Self(std::marker::PhantomData))
+            }
+        }
+
+        let services = ServiceCollection::new()
+            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(transient_generic::<Cache<()>>())
+            .build_strict_provider()
+            .unwrap();
+
+        // act
+        let _ = services.get_required_generic::<Cache<User>>();
+
+        // assert
+        services.assert_dependencies_declared();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "One or more services resolved dependencies that have not been declared.\n  \
+                               [1] Service 'di::test::OtherTestServiceImpl' resolved dependent service \
+                               'dyn di::test::TestService', which has not been declared"
+    )]
+    fn assert_dependencies_declared_should_panic_when_dependency_is_undeclared() {
+        // arrange
+        let services = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                scoped::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .build_strict_provider()
+            .unwrap();
+
+        // act
+        let _ = services.create_scope().get_required::<dyn OtherTestService>();
+
+        // assert
+        services.assert_dependencies_declared();
+    }
+
+    #[test]
+    fn service_provider_should_drop_existing_as_service() {
+        // arrange
+        let file = new_temp_file("drop2");
+
+        // act
+        {
+            let mut services = ServiceCollection::new();
+            services.add(existing_as_self(Droppable::new(file.clone())));
+            let _ = services.build_provider().unwrap();
+        }
+
+        // assert
+        let dropped = !file.exists();
+        remove_file(&file).ok();
+        assert!(dropped);
+    }
+
+    #[test]
+    fn service_provider_should_drop_lazy_initialized_service() {
+        // arrange
+        let file = new_temp_file("drop3");
+
+        // act
+        {
+            let provider = ServiceCollection::new()
+                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+                .add(singleton_as_self().from(|sp| Ref::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))))
+                .build_provider()
+                .unwrap();
+            let _ = provider.get_required::<Droppable>();
+        }
+
+        // assert
+        let dropped = !file.exists();
+        remove_file(&file).ok();
+        assert!(dropped);
+    }
+
+    #[test]
+    fn service_provider_should_not_drop_service_if_never_instantiated() {
+        // arrange
+        let file = new_temp_file("drop5");
+
+        // act
+        {
+            let _ = ServiceCollection::new()
+                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
+                .add(singleton_as_self().from(|sp| Ref::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))))
+                .build_provider()
+                .unwrap();
+        }
+
+        // assert
+        let not_dropped = file.exists();
+        remove_file(&file).ok();
+        assert!(not_dropped);
+    }
+
+    #[test]
+    fn dispose_should_dispose_scoped_services_in_reverse_order() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let (log1, log2) = (log.clone(), log.clone());
+        let provider = ServiceCollection::new()
+            .add(
+                scoped_with_key::<key::Thing1, Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("1", log1.clone()))),
+            )
+            .add(
+                scoped_with_key::<key::Thing2, Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("2", log2.clone()))),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+        let _ = scope.get_required_by_key::<key::Thing1, Disposer>();
+        let _ = scope.get_required_by_key::<key::Thing2, Disposer>();
+
+        // act
+        scope.dispose();
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["2", "1"]);
+    }
+
+    #[test]
+    fn dispose_should_dispose_dependent_service_before_its_dependency() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let (log1, log2) = (log.clone(), log.clone());
+        let provider = ServiceCollection::new()
+            .add(
+                scoped_with_key::<key::Thing1, Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("dependency", log1.clone()))),
+            )
+            .add(
+                scoped::<Disposer, Disposer>()
+                    .depends_on(exactly_one_with_key::<key::Thing1, Disposer>())
+                    .disposable()
+                    .from(move |sp| {
+                        let _ = sp.get_required_by_key::<key::Thing1, Disposer>();
+                        Ref::new(Disposer::new("dependent", log2.clone()))
+                    }),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+        let _ = scope.get_required::<Disposer>();
+
+        // act
+        scope.dispose();
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["dependent", "dependency"]);
+    }
+
+    #[test]
+    fn drop_should_dispose_services_owned_by_scope() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("transient", log1.clone()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        {
+            let scope = provider.create_scope();
+            let _ = scope.get_required::<Disposer>();
+            let _ = scope.get_required::<Disposer>();
+        }
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["transient", "transient"]);
+    }
+
+    #[test]
+    fn drop_should_not_dispose_singleton_resolved_from_scope() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("singleton", log1.clone()))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        {
+            let scope = provider.create_scope();
+            let _ = scope.get_required::<Disposer>();
+        }
+
+        let disposed_by_scope = log.lock().unwrap().len();
+        drop(provider);
+
+        // assert
+        assert_eq!(disposed_by_scope, 0);
+        assert_eq!(&*log.lock().unwrap(), &["singleton"]);
+    }
+
+    #[test]
+    fn drop_should_dispose_singleton_when_lazy_dependency_captures_provider() {
+        // arrange
+        struct Holder(crate::lazy::Lazy<Ref<Disposer>>);
+
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("singleton", log1.clone()))),
+            )
+            .add(singleton_as_self::<Holder>().from(|sp| Ref::new(Holder(crate::lazy::exactly_one(sp.clone())))))
+            .build_provider()
+            .unwrap();
+        let _ = provider.get_required::<Holder>().0.value();
+
+        // act
+        drop(provider);
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["singleton"]);
+    }
+
+    #[test]
+    fn drop_should_dispose_scoped_service_when_factory_dependency_captures_provider() {
+        // arrange
+        struct Holder(crate::factory::Factory<Ref<Disposer>>);
+
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                scoped::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("scoped", log1.clone()))),
+            )
+            .add(scoped::<Holder, Holder>().from(|sp| Ref::new(Holder(crate::factory::exactly_one(sp.clone())))))
+            .build_provider()
+            .unwrap();
+
+        // act
+        {
+            let scope = provider.create_scope();
+            let _ = scope.get_required::<Holder>().0.create();
+        }
+
+        // assert
+        assert_eq!(&*log.lock().unwrap(), &["scoped"]);
+    }
+
+    #[test]
+    fn drop_should_not_dispose_service_until_last_clone_is_dropped() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                scoped::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("scoped", log1.clone()))),
+            )
+            .build_provider()
+            .unwrap()
+            .create_scope();
+        let clone = provider.clone();
+        let _ = provider.get_required::<Disposer>();
+
+        // act
+        drop(provider);
+
+        // assert
+        assert!(log.lock().unwrap().is_empty());
+        drop(clone);
+        assert_eq!(&*log.lock().unwrap(), &["scoped"]);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn dispose_async_should_dispose_services_in_reverse_order() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let (log1, log2) = (log.clone(), log.clone());
+        let provider = ServiceCollection::new()
+            .add(
+                scoped::<Disposer, Disposer>()
+                    .disposable()
+                    .from(move |_| Ref::new(Disposer::new("sync", log1.clone()))),
+            )
+            .add(
+                scoped::<AsyncDisposer, AsyncDisposer>()
+                    .async_disposable()
+                    .from(move |_| Ref::new(AsyncDisposer::new("async", log2.clone(), false))),
+            )
+            .build_provider()
+            .unwrap();
+        let scope = provider.create_scope();
+        let _ = scope.get_required::<Disposer>();
+        let _ = scope.get_required::<AsyncDisposer>();
+
+        // act
+        let result = block_on(scope.dispose_async());
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(&*log.lock().unwrap(), &["async", "sync"]);
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn dispose_async_should_report_all_failures() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let (log1, log2) = (log.clone(), log.clone());
+        let provider = ServiceCollection::new()
+            .add(
+                scoped_with_key::<key::Thing1, AsyncDisposer, AsyncDisposer>()
+                    .async_disposable()
+                    .from(move |_| Ref::new(AsyncDisposer::new("1", log1.clone(), true))),
+            )
+            .add(
+                scoped_with_key::<key::Thing2, AsyncDisposer, AsyncDisposer>()
+                    .async_disposable()
+                    .from(move |_| Ref::new(AsyncDisposer::new("2", log2.clone(), true))),
+            )
+            .build_provider()
+            .unwrap()
+            .create_scope();
+        let _ = provider.get_required_by_key::<key::Thing1, AsyncDisposer>();
+        let _ = provider.get_required_by_key::<key::Thing2, AsyncDisposer>();
+
+        // act
+        let result = block_on(provider.dispose_async());
+
+        // assert
+        let error = result.unwrap_err();
+        assert_eq!(error.errors().len(), 2);
+        assert_eq!(
+            &error.to_string(),
+            "One or more services failed to be disposed.\n  [1] 2 failed\n  [2] 1 failed"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn drop_should_not_dispose_async_disposable_service() {
+        // arrange
+        let log = DisposeLog::new(Mutex::new(Vec::new()));
+        let log1 = log.clone();
+        let provider = ServiceCollection::new()
+            .add(
+                transient::<AsyncDisposer, AsyncDisposer>()
+                    .async_disposable()
+                    .from(move |_| Ref::new(AsyncDisposer::new("async", log1.clone(), false))),
+            )
+            .build_provider()
+            .unwrap();
+        let _ = provider.get_required::<AsyncDisposer>();
+
+        // act
+        drop(provider);
+
+        // assert
+        assert!(log.lock().unwrap().is_empty());
+    }
+
+    fn new_hosted_services(log: &HostLog, fail: bool) -> ServiceCollection {
+        let (log1, log2, log3) = (log.clone(), log.clone(), log.clone());
+        let mut services = ServiceCollection::new();
+
+        // cache → Thing2 → Thing1 (database)
+        services
+            .add(
+                singleton::<dyn HostedService, Thing3>()
+                    .depends_on(exactly_one::<Thing2>())
+                    .from(move |_| Ref::new(Host::new("cache", log1.clone(), fail))),
+            )
+            .add(
+                singleton::<Thing2, Thing2>()
+                    .depends_on(exactly_one::<Thing1>())
+                    .from(|_| Ref::new(Thing2)),
+            )
+            .add(singleton_as_self::<Thing1>().from(|_| Ref::new(Thing1)))
+            .add(
+                singleton::<dyn HostedService, Thing1>()
+                    .depends_on(exactly_one::<Thing1>())
+                    .from(move |_| Ref::new(Host::new("database", log2.clone(), fail))),
+            )
+            .add(
+                singleton::<dyn HostedService, TestServiceImpl>()
+                    .from(move |_| Ref::new(Host::new("worker", log3.clone(), false))),
+            );
+
+        services
+    }
+
+    #[test]
+    fn start_all_should_start_hosted_services_in_dependency_order() {
+        // arrange
+        let log = HostLog::default();
+        let provider = new_hosted_services(&log, false).build_provider().unwrap();
+
+        // act
+        let result = provider.start_all();
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(log.events(), ["start database", "start worker", "start cache"]);
+    }
+
+    #[test]
+    fn stop_all_should_stop_hosted_services_in_reverse_dependency_order() {
+        // arrange
+        let log = HostLog::default();
+        let provider = new_hosted_services(&log, false).build_provider().unwrap();
+
+        // act
+        let result = provider.stop_all();
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(log.events(), ["stop cache", "stop worker", "stop database"]);
+    }
+
+    #[test]
+    fn start_all_should_not_order_hosted_services_by_shared_implementation_type() {
+        // arrange
+        let log = HostLog::default();
+        let (log1, log2, log3) = (log.clone(), log.clone(), log.clone());
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn HostedService, Thing3>()
+                    .depends_on(exactly_one_with_key::<key::Thing1, dyn HostedService>())
+                    .from(move |_| Ref::new(Host::new("cache", log1.clone(), false))),
+            )
+            .add(singleton_factory::<dyn HostedService, _>(move |_| {
+                Ref::new(Host::new("database", log2.clone(), false))
+            }))
+            .add(singleton_with_key_factory::<key::Thing1, dyn HostedService, _>(
+                move |_| Ref::new(Host::new("keyed", log3.clone(), false)),
+            ))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = provider.start_all();
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(log.events(), ["start cache", "start database"]);
+    }
+
+    #[test]
+    fn start_all_should_report_all_failures() {
+        // arrange
+        let log = HostLog::default();
+        let provider = new_hosted_services(&log, true)
+            .add(singleton::<dyn HostedService, TestService2Impl>().try_from(|_| Err("Unavailable")))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let result = provider.start_all();
+
+        // assert
+        let error = result.unwrap_err();
+        assert_eq!(error.errors().len(), 3);
+        assert_eq!(log.events(), ["start database", "start worker", "start cache"]);
+        assert_eq!(
+            &error.to_string(),
+            concat!(
+                "One or more hosted services failed to start.\n",
+                "  [1] database failed\n",
+                "  [2] The factory for service 'dyn di::hosted::HostedService' failed: Unavailable\n",
+                "  [3] cache failed"
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn start_all_async_should_start_hosted_and_async_hosted_services() {
+        // arrange
+        let log = HostLog::default();
+        let log1 = log.clone();
+        let provider = new_hosted_services(&log, false)
+            .add(
+                singleton::<dyn AsyncHostedService, TestService2Impl>()
+                    .from(move |_| Ref::new(Host::new("async", log1.clone(), false))),
+            )
+            .build_provider()
+            .unwrap();
+
+        // act
+        let started = block_on(provider.start_all_async());
+        let stopped = block_on(provider.stop_all_async());
+
+        // assert
+        assert!(started.is_ok());
+        assert!(stopped.is_ok());
+        assert_eq!(
+            log.events(),
+            [
+                "start database",
+                "start worker",
+                "start async",
+                "start cache",
+                "stop cache",
+                "stop async",
+                "stop worker",
+                "stop database"
+            ]
+        );
+    }
+
+    #[test]
+    #[allow(ambiguous_wide_pointer_comparisons)]
+    fn clone_should_be_shallow() {
+        // arrange
+        let provider1 = ServiceCollection::new()
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider()
+            .unwrap();
+
+        // act
+        let provider2 = provider1.clone();
+
+        // assert
+        assert!(Ref::ptr_eq(&provider1.services, &provider2.services));
+        assert!(std::ptr::eq(provider1.services.as_ref(), provider2.services.as_ref()));
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn service_provider_should_be_send_and_sync() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let service =
+            singleton::<dyn TestService, TestAsyncServiceImpl>().from(|_| Ref::new(TestAsyncServiceImpl::default()));
+
+        services.add(service);
+
+        // act
+        let provider = services.build_provider().unwrap();
+
+        // assert
+        assert_send_and_sync(provider);
+    }
+
+    #[cfg(feature = "async")]
+    #[derive(Clone)]
+    struct Holder<T: Send + Sync + Clone>(T);
+
+    #[cfg(feature = "async")]
+    fn inject<V: Send + Sync + Clone>(value: V) -> Holder<V> {
+        Holder(value)
+    }
+
+    #[test]
+    #[cfg(feature = "async")]
+    fn service_provider_should_be_async_safe() {
+        // arrange
+        let provider = ServiceCollection::new()
+            .add(
+                singleton::<dyn TestService, TestAsyncServiceImpl>()
+                    .from(|_| Ref::new(TestAsyncServiceImpl::default())),
+            )
+            .build_provider()
+            .unwrap();
+        let holder = inject(provider);
+        let h1 = holder.clone();
+        let h2 = holder.clone();
+        let value = Arc::new(Mutex::new(0));
+        let v1 = value.clone();
+        let v2 = value.clone();
+
+        // act
+        let t1 = thread::spawn(move || {
+            let service = h1.0.get_required::<dyn TestService>();
+            let mut result = v1.lock().unwrap();
+            *result += service.value();
+        });
+
+        let t2 = thread::spawn(move || {
+            let service = h2.0.get_required::<dyn TestService>();
+            let mut result = v2.lock().unwrap();
+            *result += service.value();
+        });
+
+        t1.join().ok();
+        t2.join().ok();
+
+        // assert
+        assert_eq!(*value.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn validate_scopes_should_fail_when_singleton_resolves_scoped_service() {
         // arrange
-        let services = ServiceCollection::new()
-            .add(singleton_with_key::<key::Thingy, dyn Thing, Thing3>().from(|_| Ref::new(Thing3::default())))
-            .add(singleton::<dyn Thing, Thing1>().from(|_| Ref::new(Thing1::default())))
-            .build_provider()
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>().try_from(|sp| {
+                    Ok::<_, ResolveError>(Ref::new(OtherTestServiceImpl::new(
+                        sp.try_get_required::<dyn TestService>()?,
+                    )))
+                }),
+            )
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
             .unwrap();
+        let scope = provider.create_scope();
 
         // act
-        let thing = services.get_required_by_key::<key::Thingy, dyn Thing>();
+        let result = scope.try_get_required::<dyn OtherTestService>();
 
         // assert
-        assert_eq!(&thing.to_string(), "di::test::Thing3");
+        let error = result.err().unwrap();
+        assert!(matches!(
+            &error,
+            ResolveError::Captive { singleton, .. } if **singleton == Type::of::<dyn OtherTestService>()
+        ));
+        assert_eq!(error.service(), &Type::of::<dyn TestService>());
+        assert_eq!(
+            error.path(),
+            [Type::of::<dyn OtherTestService>(), Type::of::<dyn TestService>()]
+        );
     }
 
     #[test]
-    #[should_panic(expected = "No service for type 'dyn di::test::TestService' has been registered.")]
-    fn get_required_should_panic_when_service_is_unregistered() {
+    fn validate_scopes_should_fail_when_captured_provider_resolves_scoped_service() {
         // arrange
-        let services = ServiceCollection::new().build_provider().unwrap();
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(singleton_as_self::<ServiceProvider>().from(|sp| Ref::new(sp.clone())))
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
+            .unwrap();
+        let captured = provider.create_scope().get_required::<ServiceProvider>();
 
         // act
-        let _ = services.get_required::<dyn TestService>();
+        let result = captured.try_get_required::<dyn TestService>();
 
         // assert
-        // panics
+        assert!(matches!(result, Err(ResolveError::Captive { .. })));
     }
 
     #[test]
-    #[should_panic(
-        expected = "No service for type 'dyn di::test::Thing' with the key 'di::test::key::Thing1' has been registered."
-    )]
-    fn get_required_by_key_should_panic_when_service_is_unregistered() {
+    fn validate_scopes_should_allow_scoped_service_from_new_scope_of_captured_provider() {
         // arrange
-        let services = ServiceCollection::new().build_provider().unwrap();
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(singleton_as_self::<ServiceProvider>().from(|sp| Ref::new(sp.clone())))
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
+            .unwrap();
+        let captured = provider.create_scope().get_required::<ServiceProvider>();
 
         // act
-        let _ = services.get_required_by_key::<key::Thing1, dyn Thing>();
+        let result = captured.create_scope().try_get_required::<dyn TestService>();
 
         // assert
-        // panics
+        assert!(result.is_ok());
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn get_should_return_same_instance_for_singleton_service() {
+    fn provider_should_allow_singleton_to_resolve_scoped_service_when_scopes_are_not_validated() {
         // arrange
-        let services = ServiceCollection::new()
-            .add(existing::<dyn TestService, TestServiceImpl>(Box::new(
-                TestServiceImpl::default(),
-            )))
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
             .add(
                 singleton::<dyn OtherTestService, OtherTestServiceImpl>()
                     .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
             )
-            .build_provider()
+            .build_provider_with(ValidationOptions::default().validate_scopes(false))
             .unwrap();
 
         // act
-        let svc2 = services.get_required::<dyn OtherTestService>();
-        let svc1 = services.get_required::<dyn OtherTestService>();
+        let result = provider.create_scope().try_get_required::<dyn OtherTestService>();
 
         // assert
-        assert!(Ref::ptr_eq(&svc1, &svc2));
+        assert!(result.is_ok());
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn get_should_return_different_instances_for_transient_service() {
+    fn try_get_required_should_return_error_when_scoped_service_is_resolved_from_root() {
         // arrange
-        let services = ServiceCollection::new()
-            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
-            .build_provider()
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>().try_from(|sp| {
+                    Ok::<_, ResolveError>(Ref::new(OtherTestServiceImpl::new(
+                        sp.try_get_required::<dyn TestService>()?,
+                    )))
+                }),
+            )
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
             .unwrap();
 
         // act
-        let svc1 = services.get_required::<dyn TestService>();
-        let svc2 = services.get_required::<dyn TestService>();
-
-        // assert
-        assert!(!Ref::ptr_eq(&svc1, &svc2));
-    }
-
-    #[test]
-    fn get_all_should_return_all_services() {
-        // arrange
-        let mut collection = ServiceCollection::new();
-
-        collection
-            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl { value: 1 })))
-            .add(singleton::<dyn TestService, TestService2Impl>().from(|_| Ref::new(TestService2Impl { value: 2 })));
-
-        let provider = collection.build_provider().unwrap();
-
-        // act
-        let services = provider.get_all::<dyn TestService>();
-        let values: Vec<_> = services.map(|s| s.value()).collect();
+        let error = provider.try_get_required::<dyn OtherTestService>().err().unwrap();
 
         // assert
-        assert_eq!(&values, &[1, 2]);
+        assert!(matches!(error, ResolveError::Unscoped { .. }));
+        assert_eq!(
+            error.to_string(),
+            "The scoped service 'dyn di::test::TestService' cannot be resolved from the root service provider.\n  \
+             Resolution path: dyn di::test::OtherTestService -> dyn di::test::TestService"
+        );
     }
 
     #[test]
-    fn get_all_by_key_should_return_all_services() {
+    fn try_get_required_should_return_error_when_scoped_service_is_resolved_from_root_of_child() {
         // arrange
-        let mut collection = ServiceCollection::new();
-
-        collection
-            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing1>().from(|_| Ref::new(Thing1::default())))
-            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing2>().from(|_| Ref::new(Thing2::default())))
-            .add(singleton_with_key::<key::Thingies, dyn Thing, Thing3>().from(|_| Ref::new(Thing3::default())));
-
-        let provider = collection.build_provider().unwrap();
+        let parent = ServiceCollection::new()
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
+            .unwrap();
+        let child = parent
+            .create_child(|services| {
+                services
+                    .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())));
+            })
+            .unwrap();
 
         // act
-        let services = provider.get_all_by_key::<key::Thingies, dyn Thing>();
-        let values: Vec<_> = services.map(|s| s.to_string()).collect();
+        let result = child.try_get_required::<dyn TestService>();
 
         // assert
-        assert_eq!(
-            &values,
-            &[
-                "di::test::Thing1".to_owned(),
-                "di::test::Thing2".to_owned(),
-                "di::test::Thing3".to_owned()
-            ]
-        );
+        assert!(matches!(result, Err(ResolveError::Unscoped { .. })));
+        assert!(child.create_scope().try_get_required::<dyn TestService>().is_ok());
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn two_scoped_service_providers_should_create_different_instances() {
+    #[should_panic(expected = "cannot be resolved from the root service provider")]
+    fn get_should_panic_when_scoped_service_is_resolved_from_root() {
         // arrange
-        let services = ServiceCollection::new()
+        let provider = ServiceCollection::new()
             .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
-            .build_provider()
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
             .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = services.create_scope();
 
         // act
-        let svc1 = scope1.get_required::<dyn TestService>();
-        let svc2 = scope2.get_required::<dyn TestService>();
+        let _ = provider.get::<dyn TestService>();
 
         // assert
-        assert!(!Ref::ptr_eq(&svc1, &svc2));
+        // panics
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn parent_child_scoped_service_providers_should_create_different_instances() {
+    #[should_panic(expected = "cannot be resolved from the root service provider")]
+    fn get_all_should_panic_when_scoped_service_is_resolved_from_root() {
         // arrange
-        let services = ServiceCollection::new()
+        let provider = ServiceCollection::new()
             .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
-            .build_provider()
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
             .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
 
         // act
-        let svc1 = scope1.get_required::<dyn TestService>();
-        let svc2 = scope2.get_required::<dyn TestService>();
+        let _ = provider.get_all::<dyn TestService>().collect::<Vec<_>>();
 
         // assert
-        assert!(!Ref::ptr_eq(&svc1, &svc2));
+        // panics
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn scoped_service_provider_should_have_same_singleton_when_eager_created_in_parent() {
+    fn get_should_resolve_scoped_service_from_scope_when_scopes_are_validated() {
         // arrange
-        let services = ServiceCollection::new()
-            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
-            .build_provider()
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider_with(ValidationOptions::default().validate_scopes(true))
             .unwrap();
-        let svc1 = services.get_required::<dyn TestService>();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
 
         // act
-        let svc2 = scope1.get_required::<dyn TestService>();
-        let svc3 = scope2.get_required::<dyn TestService>();
+        let service = provider.create_scope().get::<dyn TestService>();
 
         // assert
-        assert!(Ref::ptr_eq(&svc1, &svc2));
-        assert!(Ref::ptr_eq(&svc1, &svc3));
+        assert!(service.is_some());
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn scoped_service_provider_should_have_same_singleton_when_lazy_created_in_parent() {
+    fn build_provider_should_validate_scopes_according_to_build_profile() {
         // arrange
-        let services = ServiceCollection::new()
-            .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
             .build_provider()
             .unwrap();
-        let scope1 = services.create_scope();
-        let scope2 = scope1.create_scope();
-        let svc1 = services.get_required::<dyn TestService>();
 
         // act
-        let svc2 = scope1.get_required::<dyn TestService>();
-        let svc3 = scope2.get_required::<dyn TestService>();
+        let result = provider.try_get_required::<dyn TestService>();
 
         // assert
-        assert!(Ref::ptr_eq(&svc1, &svc2));
-        assert!(Ref::ptr_eq(&svc1, &svc3));
-    }
-
-    #[test]
-    fn service_provider_should_drop_existing_as_service() {
-        // arrange
-        let file = new_temp_file("drop2");
-
-        // act
-        {
-            let mut services = ServiceCollection::new();
-            services.add(existing_as_self(Droppable::new(file.clone())));
-            let _ = services.build_provider().unwrap();
+        if cfg!(debug_assertions) {
+            assert!(matches!(result, Err(ResolveError::Unscoped { .. })));
+        } else {
+            assert!(result.is_ok());
         }
-
-        // assert
-        let dropped = !file.exists();
-        remove_file(&file).ok();
-        assert!(dropped);
     }
 
     #[test]
-    fn service_provider_should_drop_lazy_initialized_service() {
+    fn get_should_resolve_scoped_service_from_root_when_scopes_are_not_validated() {
         // arrange
-        let file = new_temp_file("drop3");
+        let provider = ServiceCollection::new()
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .build_provider_with(ValidationOptions::default().validate_scopes(false))
+            .unwrap();
 
         // act
-        {
-            let provider = ServiceCollection::new()
-                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
-                .add(singleton_as_self().from(|sp| Ref::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))))
-                .build_provider()
-                .unwrap();
-            let _ = provider.get_required::<Droppable>();
-        }
+        let service = provider.get::<dyn TestService>();
 
         // assert
-        let dropped = !file.exists();
-        remove_file(&file).ok();
-        assert!(dropped);
+        assert!(service.is_some());
     }
 
-    #[test]
-    fn service_provider_should_not_drop_service_if_never_instantiated() {
-        // arrange
-        let file = new_temp_file("drop5");
+    type ObservedEvents = Ref<Mutex<Vec<(Type, bool, Option<Type>)>>>;
 
-        // act
-        {
-            let _ = ServiceCollection::new()
-                .add(existing::<Path, PathBuf>(file.clone().into_boxed_path()))
-                .add(singleton_as_self().from(|sp| Ref::new(Droppable::new(sp.get_required::<Path>().to_path_buf()))))
-                .build_provider()
-                .unwrap();
-        }
+    fn observe(provider: ServiceProvider) -> (ServiceProvider, ObservedEvents) {
+        let events = ObservedEvents::default();
+        let log = events.clone();
+        let provider = provider.with_observer(move |event: &crate::ResolutionEvent| {
+            log.lock()
+                .unwrap()
+                .push((event.service_type().clone(), event.is_cached(), event.parent().cloned()));
+        });
 
-        // assert
-        let not_dropped = file.exists();
-        remove_file(&file).ok();
-        assert!(not_dropped);
+        (provider, events)
     }
 
     #[test]
-    #[allow(clippy::vtable_address_comparisons)]
-    fn clone_should_be_shallow() {
+    fn observer_should_be_notified_of_activated_and_cached_services() {
         // arrange
-        let provider1 = ServiceCollection::new()
-            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
-            .build_provider()
-            .unwrap();
+        let (provider, events) = observe(
+            ServiceCollection::new()
+                .add(singleton::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+                .build_provider()
+                .unwrap(),
+        );
 
         // act
-        let provider2 = provider1.clone();
+        let _ = provider.get_required::<dyn TestService>();
+        let _ = provider.get_required::<dyn TestService>();
 
         // assert
-        assert!(Ref::ptr_eq(&provider1.services, &provider2.services));
-        assert!(std::ptr::eq(provider1.services.as_ref(), provider2.services.as_ref()));
+        let service = Type::of::<dyn TestService>();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![(service.clone(), false, None), (service, true, None)]
+        );
     }
 
     #[test]
-    #[cfg(feature = "async")]
-    fn service_provider_should_be_send_and_sync() {
+    fn observer_should_be_notified_of_parent_resolution() {
         // arrange
-        let mut services = ServiceCollection::new();
-        let service =
-            singleton::<dyn TestService, TestAsyncServiceImpl>().from(|_| Ref::new(TestAsyncServiceImpl::default()));
-
-        services.add(service);
+        let (provider, events) = observe(
+            ServiceCollection::new()
+                .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+                .add(
+                    transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                        .depends_on(exactly_one::<dyn TestService>())
+                        .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+                )
+                .build_provider()
+                .unwrap(),
+        );
 
         // act
-        let provider = services.build_provider().unwrap();
+        let _ = provider.get_required::<dyn OtherTestService>();
 
         // assert
-        assert_send_and_sync(provider);
-    }
-
-    #[cfg(feature = "async")]
-    #[derive(Clone)]
-    struct Holder<T: Send + Sync + Clone>(T);
-
-    #[cfg(feature = "async")]
-    fn inject<V: Send + Sync + Clone>(value: V) -> Holder<V> {
-        Holder(value)
+        let parent = Type::of::<dyn OtherTestService>();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (Type::of::<dyn TestService>(), false, Some(parent.clone())),
+                (parent, false, None)
+            ]
+        );
     }
 
     #[test]
-    #[cfg(feature = "async")]
-    fn service_provider_should_be_async_safe() {
+    fn observer_should_be_shared_by_scope() {
         // arrange
-        let provider = ServiceCollection::new()
-            .add(
-                singleton::<dyn TestService, TestAsyncServiceImpl>()
-                    .from(|_| Ref::new(TestAsyncServiceImpl::default())),
-            )
-            .build_provider()
-            .unwrap();
-        let holder = inject(provider);
-        let h1 = holder.clone();
-        let h2 = holder.clone();
-        let value = Arc::new(Mutex::new(0));
-        let v1 = value.clone();
-        let v2 = value.clone();
+        let (provider, events) = observe(
+            ServiceCollection::new()
+                .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+                .build_provider()
+                .unwrap(),
+        );
+        let scope = provider.create_scope();
 
         // act
-        let t1 = thread::spawn(move || {
-            let service = h1.0.get_required::<dyn TestService>();
-            let mut result = v1.lock().unwrap();
-            *result += service.value();
-        });
-
-        let t2 = thread::spawn(move || {
-            let service = h2.0.get_required::<dyn TestService>();
-            let mut result = v2.lock().unwrap();
-            *result += service.value();
-        });
-
-        t1.join().ok();
-        t2.join().ok();
+        let _ = scope.get_required::<dyn TestService>();
+        let _ = provider.create_scope().get_required::<dyn TestService>();
 
         // assert
-        assert_eq!(*value.lock().unwrap(), 3);
+        assert_eq!(
+            events.lock().unwrap().iter().filter(|(_, cached, _)| !cached).count(),
+            2
+        );
     }
 }
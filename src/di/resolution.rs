@@ -0,0 +1,278 @@
+use crate::r#type::expand_type;
+use crate::Type;
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::sync::Arc;
+
+/// Represents the possible errors that can occur when a service is resolved.
+///
+/// # Remarks
+///
+/// Every error carries the resolution path that led to it. The path starts with the requested service and ends with
+/// the service that failed, which makes it possible to identify a failure deep within an object graph.
+#[derive(Clone, Debug)]
+pub enum ResolveError {
+    /// Indicates that a required service has not been registered.
+    Missing {
+        /// The [type](Type) of the missing service.
+        service: Type,
+
+        /// The resolution path to the missing service.
+        path: Vec<Type>,
+    },
+
+    /// Indicates that an activated service could not be converted to the requested type.
+    TypeMismatch {
+        /// The [type](Type) of the mismatched service.
+        service: Type,
+
+        /// The resolution path to the mismatched service.
+        path: Vec<Type>,
+    },
+
+    /// Indicates that a service was requested again while it was being activated.
+    Circular {
+        /// The [type](Type) of the service that was requested again.
+        service: Type,
+
+        /// The resolution path, which ends with the service that was requested again.
+        path: Vec<Type>,
+    },
+
+    /// Indicates that a scoped service was requested by a singleton service, which would capture it.
+    Captive {
+        /// The [type](Type) of the scoped service.
+        service: Type,
+
+        /// The [type](Type) of the singleton service that requested the scoped service.
+        singleton: Box<Type>,
+
+        /// The resolution path to the scoped service.
+        path: Vec<Type>,
+    },
+
+    /// Indicates that a scoped service was requested from the root service provider rather than a scope.
+    Unscoped {
+        /// The [type](Type) of the scoped service.
+        service: Type,
+
+        /// The resolution path to the scoped service.
+        path: Vec<Type>,
+    },
+
+    /// Indicates that the factory function used to activate a service failed.
+    Factory {
+        /// The [type](Type) of the service whose factory failed.
+        service: Type,
+
+        /// The resolution path to the failed service.
+        path: Vec<Type>,
+
+        /// The error that caused the factory to fail.
+        error: Arc<dyn Error + Send + Sync>,
+    },
+}
+
+impl ResolveError {
+    /// Gets the [type](Type) of the service that could not be resolved.
+    pub fn service(&self) -> &Type {
+        match self {
+            Self::Missing { service, .. }
+            | Self::TypeMismatch { service, .. }
+            | Self::Circular { service, .. }
+            | Self::Captive { service, .. }
+            | Self::Unscoped { service, .. }
+            | Self::Factory { service, .. } => service,
+        }
+    }
+
+    /// Gets the resolution path that led to the error.
+    pub fn path(&self) -> &[Type] {
+        match self {
+            Self::Missing { path, .. }
+            | Self::TypeMismatch { path, .. }
+            | Self::Circular { path, .. }
+            | Self::Captive { path, .. }
+            | Self::Unscoped { path, .. }
+            | Self::Factory { path, .. } => path,
+        }
+    }
+
+    pub(crate) fn missing(service: Type) -> Self {
+        let path = path_to(&service);
+        Self::Missing { service, path }
+    }
+
+    pub(crate) fn type_mismatch(service: Type) -> Self {
+        let path = path_to(&service);
+        Self::TypeMismatch { service, path }
+    }
+
+    pub(crate) fn captive(service: Type, singleton: Type) -> Self {
+        let path = path_to(&service);
+        Self::Captive {
+            service,
+            singleton: Box::new(singleton),
+            path,
+        }
+    }
+
+    pub(crate) fn unscoped(service: Type) -> Self {
+        let path = path_to(&service);
+        Self::Unscoped { service, path }
+    }
+
+    // a factory only fails while its service is being activated so the service is always the innermost
+    // activation on the current thread. a factory that fails to resolve one of its own dependencies
+    // reports that failure as is
+    #[cfg(any(feature = "builder", feature = "inject"))]
+    pub(crate) fn factory(error: Box<dyn Error + Send + Sync>) -> Self {
+        match error.downcast::<Self>() {
+            Ok(error) => *error,
+            Err(error) => {
+                let path = current_path();
+
+                Self::Factory {
+                    service: path.last().cloned().unwrap_or_else(Type::unknown),
+                    path,
+                    error: Arc::from(error),
+                }
+            }
+        }
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        match self {
+            Self::Missing { service, .. } => write!(
+                formatter,
+                "No service for type {} has been registered.",
+                expand_type(service)
+            )?,
+            Self::TypeMismatch { service, .. } => write!(
+                formatter,
+                "The service for type {} could not be converted to the requested type.",
+                expand_type(service)
+            )?,
+            Self::Circular { service, .. } => write!(
+                formatter,
+                "A circular dependency was detected for service {}.",
+                expand_type(service)
+            )?,
+            Self::Captive { service, singleton, .. } => write!(
+                formatter,
+                "The scoped service {} cannot be resolved for the singleton service {}, which would capture it.",
+                expand_type(service),
+                expand_type(singleton)
+            )?,
+            Self::Unscoped { service, .. } => write!(
+                formatter,
+                "The scoped service {} cannot be resolved from the root service provider.",
+                expand_type(service)
+            )?,
+            Self::Factory { service, error, .. } => write!(
+                formatter,
+                "The factory for service {} failed: {}",
+                expand_type(service),
+                error
+            )?,
+        }
+
+        let path = self.path();
+
+        if path.len() > 1 {
+            write!(formatter, "\n  Resolution path: {}", path[0])?;
+
+            for service in &path[1..] {
+                write!(formatter, " -> {}", service)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for ResolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Factory { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+struct Frame {
+    id: usize,
+    service: Type,
+}
+
+thread_local! {
+    static FRAMES: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+#[cfg(any(feature = "builder", feature = "inject"))]
+fn current_path() -> Vec<Type> {
+    FRAMES.with_borrow(|frames| frames.iter().map(|frame| frame.service.clone()).collect())
+}
+
+fn path_to(service: &Type) -> Vec<Type> {
+    FRAMES.with_borrow(|frames| {
+        frames
+            .iter()
+            .map(|frame| frame.service.clone())
+            .chain(std::iter::once(service.clone()))
+            .collect()
+    })
+}
+
+/// Gets the type of the service being activated on the current thread, if any.
+pub(crate) fn current_service() -> Option<Type> {
+    FRAMES.with_borrow(|frames| frames.last().map(|frame| frame.service.clone()))
+}
+
+/// Gets the identifier of the service being activated on the current thread, if any.
+pub(crate) fn current() -> Option<usize> {
+    FRAMES.with_borrow(|frames| frames.last().map(|frame| frame.id))
+}
+
+/// Represents the activation of a service on the current thread.
+pub(crate) struct Activation;
+
+impl Activation {
+    // a service that is requested again while it is being activated on the same thread would otherwise recurse until
+    // the stack overflows or, for a cached service, deadlock while its instance is initialized
+    pub fn enter(id: usize, service: &Type) -> Result<Self, ResolveError> {
+        if FRAMES.with_borrow(|frames| frames.iter().any(|frame| frame.id == id)) {
+            return Err(ResolveError::Circular {
+                service: service.clone(),
+                path: path_to(service),
+            });
+        }
+
+        FRAMES.with_borrow_mut(|frames| {
+            frames.push(Frame {
+                id,
+                service: service.clone(),
+            })
+        });
+
+        Ok(Self)
+    }
+}
+
+impl Drop for Activation {
+    #[inline]
+    fn drop(&mut self) {
+        FRAMES.with_borrow_mut(Vec::pop);
+    }
+}
+
+/// Unwraps the result of a resolution, panicking with the error if it failed.
+pub(crate) fn expect<T>(result: Result<T, ResolveError>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(error) => panic!("{}", error),
+    }
+}
@@ -0,0 +1,120 @@
+use crate::r#type::expand_type;
+use crate::{resolution, ServiceDescriptor, Type};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::sync::{Mutex, PoisonError, RwLock};
+
+/// Represents a service that was resolved during the activation of another service without being declared as one
+/// of its [dependencies](crate::ServiceDependency).
+#[derive(Clone, Debug, PartialEq)]
+pub struct UndeclaredDependency {
+    service: Type,
+    implementation: Type,
+    dependency: Type,
+}
+
+impl UndeclaredDependency {
+    /// Gets the [type](Type) of the service that resolved the undeclared dependency.
+    #[inline]
+    pub fn service_type(&self) -> &Type {
+        &self.service
+    }
+
+    /// Gets the implementation [type](Type) of the service that resolved the undeclared dependency.
+    #[inline]
+    pub fn implementation_type(&self) -> &Type {
+        &self.implementation
+    }
+
+    /// Gets the [type](Type) of the undeclared dependency.
+    #[inline]
+    pub fn dependency_type(&self) -> &Type {
+        &self.dependency
+    }
+}
+
+impl Display for UndeclaredDependency {
+    fn fmt(&self, formatter: &mut Formatter) -> FormatResult {
+        write!(
+            formatter,
+            "Service '{}' resolved dependent service {}, which has not been declared",
+            self.implementation.name(),
+            expand_type(&self.dependency)
+        )
+    }
+}
+
+struct Declaration {
+    service: Type,
+    implementation: Type,
+    dependencies: Vec<Type>,
+}
+
+/// Represents the record of the services resolved by each service while it is activated.
+pub(crate) struct Strict {
+    declarations: RwLock<HashMap<usize, Declaration>>,
+    undeclared: Mutex<Vec<UndeclaredDependency>>,
+}
+
+impl Declaration {
+    fn of(descriptor: &ServiceDescriptor) -> Self {
+        Self {
+            service: descriptor.service_type().clone(),
+            implementation: descriptor.implementation_type().clone(),
+            dependencies: descriptor
+                .dependencies()
+                .iter()
+                .map(|dependency| dependency.injected_type().clone())
+                .collect(),
+        }
+    }
+}
+
+impl Strict {
+    pub fn new<'a>(descriptors: impl Iterator<Item = &'a ServiceDescriptor>) -> Self {
+        let declarations = descriptors
+            .map(|descriptor| (descriptor.id(), Declaration::of(descriptor)))
+            .collect();
+
+        Self {
+            declarations: RwLock::new(declarations),
+            undeclared: Mutex::default(),
+        }
+    }
+
+    // a closed generic type is not registered so it is declared when it is first closed
+    pub fn declare(&self, descriptor: &ServiceDescriptor) {
+        self.declarations
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(descriptor.id(), Declaration::of(descriptor));
+    }
+
+    // only a request made while a service known to this record is being
+    // activated on the current thread is attributed to that service
+    pub fn observe(&self, dependency: &Type) {
+        let declarations = self.declarations.read().unwrap_or_else(PoisonError::into_inner);
+        let Some(declaration) = resolution::current().and_then(|id| declarations.get(&id)) else {
+            return;
+        };
+
+        if declaration.dependencies.contains(dependency) {
+            return;
+        }
+
+        let undeclared = UndeclaredDependency {
+            service: declaration.service.clone(),
+            implementation: declaration.implementation.clone(),
+            dependency: dependency.clone(),
+        };
+        let mut items = self.undeclared.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if !items.contains(&undeclared) {
+            items.push(undeclared);
+        }
+    }
+
+    pub fn undeclared(&self) -> Vec<UndeclaredDependency> {
+        self.undeclared.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
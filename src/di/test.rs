@@ -1,10 +1,11 @@
-use crate::{KeyedRef, Ref};
+use crate::{Disposable, HostedService, KeyedRef, OpenGeneric, Ref, ServiceProvider};
 use std::any::type_name;
 use std::env;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::fs::{remove_file, File};
+use std::marker::PhantomData;
 use std::path::PathBuf;
 
-#[cfg(feature = "async")]
 use std::sync::Mutex;
 
 pub(crate) fn new_temp_file(name: &str) -> PathBuf {
@@ -88,6 +89,151 @@ impl Drop for Droppable {
     }
 }
 
+// records the order in which services are activated
+#[derive(Clone, Default)]
+pub(crate) struct ActivationLog(Ref<Mutex<Vec<&'static str>>>);
+
+impl ActivationLog {
+    pub fn activated(&self, name: &'static str) {
+        self.0.lock().unwrap().push(name);
+    }
+
+    pub fn order(&self) -> Vec<&'static str> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+pub(crate) type DisposeLog = Ref<Mutex<Vec<&'static str>>>;
+
+pub(crate) struct Disposer {
+    name: &'static str,
+    log: DisposeLog,
+}
+
+impl Disposer {
+    pub fn new(name: &'static str, log: DisposeLog) -> Self {
+        Self { name, log }
+    }
+}
+
+impl Disposable for Disposer {
+    fn dispose(&self) {
+        self.log.lock().unwrap().push(self.name);
+    }
+}
+
+// records the order in which hosted services are started and stopped
+#[derive(Clone, Default)]
+pub(crate) struct HostLog(Ref<Mutex<Vec<String>>>);
+
+impl HostLog {
+    pub fn events(&self) -> Vec<String> {
+        self.0.lock().unwrap().clone()
+    }
+
+    fn record(&self, event: &str, name: &str) {
+        self.0.lock().unwrap().push(format!("{} {}", event, name));
+    }
+}
+
+pub(crate) struct Host {
+    name: &'static str,
+    log: HostLog,
+    fail: bool,
+}
+
+impl Host {
+    pub fn new(name: &'static str, log: HostLog, fail: bool) -> Self {
+        Self { name, log, fail }
+    }
+
+    fn record(&self, event: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.log.record(event, self.name);
+
+        if self.fail {
+            Err(format!("{} failed", self.name).into())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl HostedService for Host {
+    fn start(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.record("start")
+    }
+
+    fn stop(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.record("stop")
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::AsyncHostedService for Host {
+    fn start_async(&self) -> crate::HostFuture<'_> {
+        Box::pin(async move { self.record("start") })
+    }
+
+    fn stop_async(&self) -> crate::HostFuture<'_> {
+        Box::pin(async move { self.record("stop") })
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+    use std::thread::{self, Thread};
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark()
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut context = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut context) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) struct AsyncDisposer {
+    name: &'static str,
+    log: DisposeLog,
+    fail: bool,
+}
+
+#[cfg(feature = "async")]
+impl AsyncDisposer {
+    pub fn new(name: &'static str, log: DisposeLog, fail: bool) -> Self {
+        Self { name, log, fail }
+    }
+}
+
+#[cfg(feature = "async")]
+impl crate::AsyncDisposable for AsyncDisposer {
+    fn dispose_async(&self) -> crate::DisposeFuture<'_> {
+        Box::pin(async move {
+            self.log.lock().unwrap().push(self.name);
+
+            if self.fail {
+                Err(format!("{} failed", self.name).into())
+            } else {
+                Ok(())
+            }
+        })
+    }
+}
+
 #[cfg(feature = "async")]
 #[derive(Default)]
 pub(crate) struct TestAsyncServiceImpl {
@@ -279,25 +425,25 @@ pub(crate) struct Thing3;
 
 impl Thing for Thing1 {}
 
-impl ToString for Thing1 {
-    fn to_string(&self) -> String {
-        String::from(type_name::<Self>())
+impl Display for Thing1 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(type_name::<Self>())
     }
 }
 
 impl Thing for Thing2 {}
 
-impl ToString for Thing2 {
-    fn to_string(&self) -> String {
-        String::from(type_name::<Self>())
+impl Display for Thing2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(type_name::<Self>())
     }
 }
 
 impl Thing for Thing3 {}
 
-impl ToString for Thing3 {
-    fn to_string(&self) -> String {
-        String::from(type_name::<Self>())
+impl Display for Thing3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(type_name::<Self>())
     }
 }
 
@@ -315,3 +461,29 @@ impl CatInTheHat {
         Self {}
     }
 }
+
+pub(crate) struct Repository<T> {
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for Repository<T> {
+    fn default() -> Self {
+        Self { _marker: PhantomData }
+    }
+}
+
+impl<T> OpenGeneric for Repository<T> {
+    type Definition = Repository<()>;
+
+    fn activate(_services: &ServiceProvider) -> Ref<Self> {
+        Ref::new(Self::default())
+    }
+}
+
+pub(crate) struct User;
+
+pub(crate) struct Order;
+
+pub(crate) struct UserService {
+    pub users: Ref<Repository<User>>,
+}
@@ -1,35 +1,216 @@
-use crate::{Ref, ServiceProvider};
-use std::any::type_name;
+use crate::{OpenGeneric, Ref, ServiceProvider};
+use std::any::{type_name, Any, TypeId};
 use std::collections::hash_map::DefaultHasher;
-use std::fmt::{Display, Formatter, Result as FormatResult};
+use std::fmt::{Debug, Display, Formatter, Result as FormatResult};
 use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+pub(crate) fn expand_type(t: &Type) -> String {
+    let (name, key) = Type::deconstruct(t);
+
+    match key {
+        Some(val) => format!("'{name}' with the key '{val}'"),
+        _ => format!("'{name}'"),
+    }
+}
+
+/// Defines the behavior of a runtime value that can be used as the key of a service.
+///
+/// # Remarks
+///
+/// This trait is implemented for any value that can be hashed, compared for equality, and displayed, and whose owned
+/// form can be shared across threads, such as an integer, a string, or an enumeration. Two keys are the same when they
+/// have the same hash and their owned forms are equal. A string slice and a [String] with the same text are the same
+/// key.
+pub trait ValueKey: Hash + Eq + Display + ToOwned<Owned: Eq + Send + Sync + 'static> {}
+
+impl<T: Hash + Eq + Display + ToOwned<Owned: Eq + Send + Sync + 'static> + ?Sized> ValueKey for T {}
+
+// the owned value of a runtime key is only compared once the hash of the key matches
+trait Key: Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    fn equals(&self, other: &dyn Key) -> bool;
+}
+
+impl<T: Eq + Send + Sync + 'static> Key for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, other: &dyn Key) -> bool {
+        other.as_any().downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+}
+
+/// Represents a key value that is only known at runtime.
+#[derive(Clone)]
+pub(crate) struct RuntimeKey {
+    text: Arc<str>,
+    hash: u64,
+    value: Arc<dyn Key>,
+}
+
+impl RuntimeKey {
+    pub fn new<K: ValueKey + ?Sized>(key: &K) -> Self {
+        let value = key.to_owned();
+
+        // a string slice is owned as a string so that the same text is the same key
+        let value: Arc<dyn Key> = match (&value as &dyn Any).downcast_ref::<&'static str>() {
+            Some(text) => Arc::new(text.to_string()),
+            None => Arc::new(value),
+        };
+
+        Self {
+            text: Arc::from(key.to_string()),
+            hash: hash_of(key),
+            value,
+        }
+    }
+}
+
+impl PartialEq for RuntimeKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.value.equals(other.value.as_ref())
+    }
+}
+
+impl Debug for RuntimeKey {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        formatter.write_str(&self.text)
+    }
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// a type, or its key, is identified by its TypeId whenever it is known at compile time. the TypeId of a
+// type that is not 'static is determined with its lifetimes erased, which matches its name. a factory
+// function and a generic type definition are distinguished from any other type so they can be recognized
+// without inspecting their names, while a runtime value key is compared by its value. the name is only
+// retained for diagnostics and never participates in equality
+#[derive(Clone, Debug)]
+enum Id {
+    Static(TypeId, &'static str),
+    Factory(TypeId, &'static str),
+    Value(Arc<RuntimeKey>),
+    Definition(TypeId, &'static str),
+}
+
+impl Id {
+    #[inline]
+    fn of<T: ?Sized>() -> Self {
+        Self::Static(typeid::of::<T>(), type_name::<T>())
+    }
+
+    #[inline]
+    fn factory_of<T: ?Sized>() -> Self {
+        Self::Factory(typeid::of::<T>(), type_name::<T>())
+    }
+
+    #[inline]
+    fn name(&self) -> &str {
+        match self {
+            Self::Static(_, name) | Self::Factory(_, name) | Self::Definition(_, name) => name,
+            Self::Value(key) => &key.text,
+        }
+    }
+}
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Static(id, _), Self::Static(other, _)) => id == other,
+            (Self::Factory(id, _), Self::Factory(other, _)) => id == other,
+            (Self::Value(key), Self::Value(other)) => key == other,
+            (Self::Definition(id, _), Self::Definition(other, _)) => id == other,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Id {}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Static(id, _) | Self::Factory(id, _) | Self::Definition(id, _) => id.hash(state),
+            Self::Value(key) => key.hash.hash(state),
+        }
+    }
+}
 
 /// Represents a type.
-#[derive(Clone, Debug, Eq)]
+///
+/// # Remarks
+///
+/// A type is compared using the [TypeId] of the type and its key, if any, so creating a type never allocates. A type
+/// is not required to be `'static`; the lifetimes of a type are not considered, which is consistent with its name.
+/// The name of a type, and its key, are only retained for diagnostic purposes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Type {
-    id: u64,
-    name: String,
-    key: Option<String>,
+    id: Id,
+    key: Option<Id>,
 }
 
 impl Type {
     /// Initializes a new instance of a type.
+    #[inline]
     pub fn of<T: ?Sized>() -> Self {
-        Type::new(type_name::<T>().to_string(), None)
+        Self {
+            id: Id::of::<T>(),
+            key: None,
+        }
     }
 
     /// Initializes a new instance of a type based on another type as a key.
+    #[inline]
     pub fn keyed<TKey, TType: ?Sized>() -> Self {
-        Type::new(type_name::<TType>().to_string(), Some(type_name::<TKey>().to_string()))
+        Self {
+            id: Id::of::<TType>(),
+            key: Some(Id::of::<TKey>()),
+        }
+    }
+
+    /// Initializes a new instance of a type based on a runtime value as a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value to use as a key
+    ///
+    /// # Remarks
+    ///
+    /// Two value keys are the same if they have the same hash and are equal. The key is displayed using its
+    /// [Display] implementation.
+    pub fn value_keyed<TType: ?Sized>(key: &(impl ValueKey + ?Sized)) -> Self {
+        Self::of::<TType>().with_value_key(key)
     }
 
     /// Initializes a new instance of a type for a factory function based
     /// on the specified return type.
+    #[inline]
     pub fn factory_of<TSvc: ?Sized>() -> Self {
-        Type::new(type_name::<dyn Fn(&ServiceProvider) -> Ref<TSvc>>().to_string(), None)
+        Self {
+            id: Id::factory_of::<dyn Fn(&ServiceProvider) -> Ref<TSvc>>(),
+            key: None,
+        }
+    }
+
+    // the definition of a generic type is the type every closed type shares
+    // through its OpenGeneric implementation
+    #[inline]
+    pub(crate) fn definition_of<T: OpenGeneric + ?Sized>() -> Self {
+        Self {
+            id: Id::Definition(typeid::of::<T::Definition>(), type_name::<T::Definition>()),
+            key: None,
+        }
     }
 
     /// Initializes a new instance for an unknown type.
+    #[inline]
     pub fn unknown() -> Self {
         Self::of::<()>()
     }
@@ -40,67 +221,241 @@ impl Type {
     ///
     /// * `key` - The type to use as a key
     pub fn with_key(&self, key: &Self) -> Self {
-        Type::new(self.name.clone(), Some(key.name.clone()))
+        Self {
+            id: self.id.clone(),
+            key: Some(key.id.clone()),
+        }
     }
 
-    fn new(name: String, key: Option<String>) -> Self {
-        let mut hasher = DefaultHasher::new();
-
-        name.hash(&mut hasher);
-
-        if let Some(ref val) = key {
-            val.hash(&mut hasher);
-        }
+    /// Creates and returns a new type based on the specified runtime value as a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The value to use as a key
+    pub fn with_value_key(&self, key: &(impl ValueKey + ?Sized)) -> Self {
+        self.with_value(&RuntimeKey::new(key))
+    }
 
+    pub(crate) fn with_value(&self, key: &RuntimeKey) -> Self {
         Self {
-            id: hasher.finish(),
-            name,
-            key,
+            id: self.id.clone(),
+            key: Some(Id::Value(Arc::new(key.clone()))),
         }
     }
 
+    #[inline]
+    pub(crate) fn is_value_keyed(&self) -> bool {
+        matches!(self.key, Some(Id::Value(..)))
+    }
+
+    // the identity of a type irrespective of its key
+    #[inline]
+    pub(crate) fn is_same_type(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+
+    #[inline]
+    pub(crate) fn is_definition(&self) -> bool {
+        matches!(self.id, Id::Definition(..))
+    }
+
+    #[inline]
+    pub(crate) fn is_factory(&self) -> bool {
+        self.key.is_none() && matches!(self.id, Id::Factory(..))
+    }
+
     /// Gets the type identifier.
     #[inline]
     pub fn id(&self) -> u64 {
-        self.id
+        hash_of(self)
     }
 
     /// Gets the type name.
     #[inline]
     pub fn name(&self) -> &str {
-        &self.name
+        self.id.name()
     }
 
     /// Deconstructs the specified type into its name component
     /// and key component, if it is defined.
     #[inline]
     pub fn deconstruct(t: &Type) -> (&str, Option<&str>) {
-        (&t.name, t.key.as_deref())
-    }
-}
-
-impl PartialEq<Type> for Type {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+        (t.name(), t.key.as_ref().map(Id::name))
     }
 }
 
 impl PartialEq<Type> for &Type {
     #[inline]
     fn eq(&self, other: &Type) -> bool {
-        self.id == other.id
+        (*self).eq(other)
     }
 }
 
-impl Hash for Type {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
+impl Display for Type {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+        formatter.write_str(self.name())
     }
 }
 
-impl Display for Type {
-    fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
-        formatter.write_str(&self.name)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn of_should_equal_same_type() {
+        // arrange
+        let type1 = Type::of::<dyn Thing>();
+
+        // act
+        let type2 = Type::of::<dyn Thing>();
+
+        // assert
+        assert_eq!(type1, type2);
+        assert_eq!(type1.id(), type2.id());
+        assert_eq!(type1.name(), "dyn di::test::Thing");
+    }
+
+    #[test]
+    fn keyed_should_not_equal_unkeyed_type() {
+        // arrange
+        let keyed = Type::keyed::<key::Thing1, dyn Thing>();
+
+        // act
+        let unkeyed = Type::of::<dyn Thing>();
+
+        // assert
+        assert_ne!(keyed, unkeyed);
+        assert_eq!(keyed, unkeyed.with_key(&Type::of::<key::Thing1>()));
+        assert_ne!(keyed, Type::keyed::<key::Thing2, dyn Thing>());
+    }
+
+    #[test]
+    fn factory_of_should_not_equal_service_type() {
+        // arrange
+        let factory = Type::factory_of::<dyn Thing>();
+
+        // act
+        let service = Type::of::<dyn Thing>();
+
+        // assert
+        assert_ne!(factory, service);
+        assert_eq!(factory, Type::factory_of::<dyn Thing>());
+        assert!(factory.is_factory());
+        assert!(!service.is_factory());
+    }
+
+    #[test]
+    fn of_should_not_be_factory_for_function_type() {
+        // arrange
+        let function = Type::of::<dyn Fn(&ServiceProvider) -> Ref<dyn Thing>>();
+
+        // act
+        let factory = Type::factory_of::<dyn Thing>();
+
+        // assert
+        assert_ne!(function, factory);
+        assert!(!function.is_factory());
+    }
+
+    #[test]
+    fn of_should_equal_same_type_with_different_lifetime() {
+        // arrange
+        fn type_of<'a>(_: &'a str) -> Type {
+            Type::of::<&'a str>()
+        }
+
+        let text = String::from("test");
+
+        // act
+        let borrowed = type_of(&text);
+
+        // assert
+        assert_eq!(borrowed, Type::of::<&'static str>());
+    }
+
+    #[test]
+    fn unknown_should_equal_unknown() {
+        // arrange
+        let mut types = HashSet::new();
+
+        // act
+        types.insert(Type::unknown());
+        types.insert(Type::unknown());
+
+        // assert
+        assert_eq!(types.len(), 1);
+        assert!(types.contains(&Type::of::<()>()));
+    }
+
+    #[test]
+    fn definition_of_should_equal_definition_of_other_closed_type() {
+        // arrange
+        let definition = Type::definition_of::<Repository<User>>();
+
+        // act
+        let other = Type::definition_of::<Repository<Order>>();
+
+        // assert
+        assert_eq!(definition, other);
+        assert!(definition.is_definition());
+        assert!(!Type::of::<Repository<User>>().is_definition());
+    }
+
+    #[test]
+    fn definition_of_should_not_equal_type_of_definition() {
+        // arrange
+        let definition = Type::definition_of::<Repository<User>>();
+
+        // act
+        let closed = Type::of::<Repository<()>>();
+
+        // assert
+        assert_ne!(definition, closed);
+        assert_eq!(definition.name(), closed.name());
+    }
+
+    #[test]
+    fn value_keyed_should_not_equal_type_with_different_key_of_same_hash() {
+        // arrange
+        #[derive(Clone, PartialEq, Eq)]
+        struct Tenant(u32);
+
+        impl Hash for Tenant {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                0.hash(state)
+            }
+        }
+
+        impl Display for Tenant {
+            fn fmt(&self, formatter: &mut Formatter<'_>) -> FormatResult {
+                write!(formatter, "tenant")
+            }
+        }
+
+        let tenant1 = Type::value_keyed::<dyn Thing>(&Tenant(1));
+
+        // act
+        let tenant2 = Type::value_keyed::<dyn Thing>(&Tenant(2));
+
+        // assert
+        assert_eq!(tenant1.id(), tenant2.id());
+        assert_ne!(tenant1, tenant2);
+        assert_eq!(tenant1, Type::value_keyed::<dyn Thing>(&Tenant(1)));
+    }
+
+    #[test]
+    fn value_keyed_should_equal_type_with_same_text_key() {
+        // arrange
+        let key = Type::value_keyed::<dyn Thing>("primary");
+
+        // act
+        let other = Type::value_keyed::<dyn Thing>(&String::from("primary"));
+
+        // assert
+        assert_eq!(key, other);
+        assert_eq!(key, Type::value_keyed::<dyn Thing>(&"primary"));
+        assert_ne!(key, Type::value_keyed::<dyn Thing>("secondary"));
     }
 }
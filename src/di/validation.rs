@@ -1,27 +1,103 @@
-use crate::{ServiceCardinality, ServiceCollection, ServiceDependency, ServiceDescriptor, ServiceLifetime, Type};
-use std::collections::{HashMap, HashSet};
+mod rules;
+
+use crate::eager::EagerActivation;
+use crate::{ServiceCollection, ServiceDependency, ServiceDescriptor, Type};
+use rules::*;
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
-fn expand_type(t: &Type) -> String {
-    let (name, key) = Type::deconstruct(t);
+/// Represents the severity of a validation result.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValidationSeverity {
+    /// Indicates a potential problem that does not prevent a service provider from being built.
+    Warning,
 
-    match key {
-        Some(val) => format!("'{name}' with the key '{val}'"),
-        _ => format!("'{name}'"),
-    }
+    /// Indicates a problem that prevents a service provider from being built.
+    Error,
 }
 
+/// Represents the result of a validation rule.
 #[derive(Clone, Debug)]
-struct ValidationResult {
+pub struct ValidationResult {
+    rule: String,
+    severity: ValidationSeverity,
+    service_type: Type,
     message: String,
 }
 
 impl ValidationResult {
-    fn fail<T: AsRef<str>>(message: T) -> Self {
+    /// Initializes a new validation result.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The identifier of the [rule](ValidationRule) that produced the result
+    /// * `severity` - The [severity](ValidationSeverity) of the result
+    /// * `service_type` - The [type](Type) of the offending service
+    /// * `message` - The validation message
+    pub fn new<S: AsRef<str>>(rule: &str, severity: ValidationSeverity, service_type: Type, message: S) -> Self {
         Self {
+            rule: String::from(rule),
+            severity,
+            service_type,
             message: String::from(message.as_ref()),
         }
     }
+
+    /// Initializes a new validation result with an [error](ValidationSeverity::Error) severity.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The identifier of the [rule](ValidationRule) that produced the result
+    /// * `service_type` - The [type](Type) of the offending service
+    /// * `message` - The validation message
+    #[inline]
+    pub fn error<S: AsRef<str>>(rule: &str, service_type: Type, message: S) -> Self {
+        Self::new(rule, ValidationSeverity::Error, service_type, message)
+    }
+
+    /// Initializes a new validation result with a [warning](ValidationSeverity::Warning) severity.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The identifier of the [rule](ValidationRule) that produced the result
+    /// * `service_type` - The [type](Type) of the offending service
+    /// * `message` - The validation message
+    #[inline]
+    pub fn warning<S: AsRef<str>>(rule: &str, service_type: Type, message: S) -> Self {
+        Self::new(rule, ValidationSeverity::Warning, service_type, message)
+    }
+
+    /// Gets the identifier of the [rule](ValidationRule) that produced the result.
+    #[inline]
+    pub fn rule(&self) -> &str {
+        &self.rule
+    }
+
+    /// Gets the [severity](ValidationSeverity) of the result.
+    #[inline]
+    pub fn severity(&self) -> ValidationSeverity {
+        self.severity
+    }
+
+    /// Gets the [type](Type) of the offending service.
+    #[inline]
+    pub fn service_type(&self) -> &Type {
+        &self.service_type
+    }
+
+    /// Gets the validation message.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ValidationResult {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), std::fmt::Error> {
+        formatter.write_str(&self.message)
+    }
 }
 
 /// Represents an validation error.
@@ -29,10 +105,15 @@ impl ValidationResult {
 pub struct ValidationError {
     message: String,
     results: Vec<ValidationResult>,
+    warnings: Vec<ValidationResult>,
 }
 
 impl ValidationError {
-    fn fail(results: Vec<ValidationResult>) -> Self {
+    pub(crate) fn fail(results: Vec<ValidationResult>) -> Self {
+        let (results, warnings): (Vec<_>, Vec<_>) = results
+            .into_iter()
+            .partition(|result| result.severity() == ValidationSeverity::Error);
+
         Self {
             message: if results.is_empty() {
                 String::from("Validation failed.")
@@ -42,8 +123,25 @@ impl ValidationError {
                 String::from("One or more validation errors occurred.")
             },
             results,
+            warnings,
         }
     }
+
+    /// Gets the [results](ValidationResult) that caused validation to fail.
+    #[inline]
+    pub fn results(&self) -> &[ValidationResult] {
+        &self.results
+    }
+
+    /// Gets the [warnings](ValidationSeverity::Warning) reported during the same validation.
+    ///
+    /// # Remarks
+    ///
+    /// Warnings do not cause validation to fail and are not part of the error message.
+    #[inline]
+    pub fn warnings(&self) -> &[ValidationResult] {
+        &self.warnings
+    }
 }
 
 impl Display for ValidationError {
@@ -66,142 +164,366 @@ impl std::error::Error for ValidationError {
     }
 }
 
-trait ValidationRule<'a> {
-    fn evaluate(&mut self, descriptor: &'a ServiceDescriptor, results: &mut Vec<ValidationResult>);
+/// Represents the context of a validation.
+pub struct ValidationContext<'a> {
+    services: &'a ServiceCollection,
+    lookup: HashMap<&'a Type, Vec<&'a ServiceDescriptor>>,
 }
 
-struct MissingRequiredType<'a> {
-    lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>,
-}
+impl<'a> ValidationContext<'a> {
+    fn new(services: &'a ServiceCollection) -> Self {
+        let mut lookup = HashMap::with_capacity(services.len());
 
-impl<'a> MissingRequiredType<'a> {
-    fn new(lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>) -> Self {
-        Self { lookup }
+        for item in services.iter() {
+            let key = item.service_type();
+            let descriptors = lookup.entry(key).or_insert_with(Vec::new);
+            descriptors.push(item);
+        }
+
+        Self { services, lookup }
     }
-}
 
-impl<'a> ValidationRule<'a> for MissingRequiredType<'a> {
-    fn evaluate(&mut self, descriptor: &'a ServiceDescriptor, results: &mut Vec<ValidationResult>) {
-        for dependency in descriptor.dependencies() {
-            if dependency.cardinality() == ServiceCardinality::ExactlyOne
-                && !self.lookup.contains_key(dependency.injected_type())
-            {
-                results.push(ValidationResult::fail(format!(
-                    "Service '{}' requires dependent service {}, which has not be registered",
-                    descriptor.implementation_type().name(),
-                    expand_type(dependency.injected_type())
-                )));
-            }
-        }
+    /// Gets the [service collection](ServiceCollection) being validated.
+    #[inline]
+    pub fn services(&self) -> &'a ServiceCollection {
+        self.services
     }
-}
 
-struct CircularDependency<'a> {
-    lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>,
-    visited: HashSet<&'a Type>,
-    queue: Vec<&'a ServiceDependency>,
-}
+    /// Gets the [service descriptors](ServiceDescriptor) registered for the specified type, in the order they were
+    /// registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The service [type](Type) to get the registrations for
+    pub fn get(&self, service_type: &Type) -> &[&'a ServiceDescriptor] {
+        self.lookup.get(service_type).map_or(&[], Vec::as_slice)
+    }
 
-impl<'a> CircularDependency<'a> {
-    fn new(lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>) -> Self {
-        Self {
-            lookup,
-            visited: HashSet::new(),
-            queue: Vec::new(),
+    /// Gets the [service descriptors](ServiceDescriptor) that satisfy the specified dependency, in the order they were
+    /// registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `dependency` - The [dependency](ServiceDependency) to get the registrations for
+    ///
+    /// # Remarks
+    ///
+    /// A dependency on a closed generic type that is not registered is satisfied by the registration of its open
+    /// generic [type definition](ServiceDependency::definition), if any.
+    pub fn resolve(&self, dependency: &ServiceDependency) -> &[&'a ServiceDescriptor] {
+        let registered = self.get(dependency.injected_type());
+
+        match dependency.definition() {
+            Some(definition) if registered.is_empty() => self.get(definition),
+            _ => registered,
         }
     }
 
-    fn check_dependency_graph(
-        &mut self,
-        root: &'a ServiceDescriptor,
-        dependency: &'a ServiceDependency,
+    /// Determines whether the specified type has been registered.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_type` - The service [type](Type) to evaluate
+    pub fn contains(&self, service_type: &Type) -> bool {
+        self.lookup.contains_key(service_type)
+    }
+}
+
+/// Defines the behavior of a validation rule.
+///
+/// # Example
+///
+/// ```
+/// use di::*;
+///
+/// trait Repository {}
+///
+/// struct RepositoryMustBeScoped;
+///
+/// impl ValidationRule for RepositoryMustBeScoped {
+///     fn id(&self) -> &str {
+///         "RepositoryMustBeScoped"
+///     }
+///
+///     fn evaluate<'a>(
+///         &self,
+///         descriptor: &'a ServiceDescriptor,
+///         _context: &ValidationContext<'a>,
+///         results: &mut Vec<ValidationResult>,
+///     ) {
+///         if descriptor.service_type() == &Type::of::<dyn Repository>()
+///             && descriptor.lifetime() != ServiceLifetime::Scoped
+///         {
+///             results.push(ValidationResult::error(
+///                 self.id(),
+///                 descriptor.service_type().clone(),
+///                 "A repository must have a scoped lifetime",
+///             ));
+///         }
+///     }
+/// }
+///
+/// let provider = ServiceCollection::new()
+///     .build_provider_with(ValidationOptions::default().add_rule(RepositoryMustBeScoped))
+///     .unwrap();
+/// ```
+pub trait ValidationRule {
+    /// Gets the identifier of the rule.
+    fn id(&self) -> &str;
+
+    /// Evaluates the rule for the specified service descriptor.
+    ///
+    /// # Arguments
+    ///
+    /// * `descriptor` - The [service descriptor](ServiceDescriptor) to evaluate
+    /// * `context` - The current [validation context](ValidationContext)
+    /// * `results` - The [results](ValidationResult) to add any validation failures to
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
         results: &mut Vec<ValidationResult>,
-    ) {
-        self.queue.clear();
-        self.queue.push(dependency);
-
-        while let Some(current) = self.queue.pop() {
-            if let Some(descriptors) = self.lookup.get(current.injected_type()) {
-                for descriptor in descriptors {
-                    if self.visited.insert(descriptor.service_type()) {
-                        self.queue.extend(descriptor.dependencies());
-                    }
-
-                    if descriptor.service_type() == root.service_type() {
-                        results.push(ValidationResult::fail(format!(
-                            "A circular dependency was detected for service {} on service '{}'",
-                            expand_type(descriptor.service_type()),
-                            root.implementation_type().name()
-                        )));
-                    }
-                }
-            }
-        }
+    );
+}
+
+// the options are retained by a provider to validate its children so rules must be
+// shareable across threads whenever a provider is
+cfg_if::cfg_if! {
+    if #[cfg(feature = "async")] {
+        type Rule = dyn ValidationRule + Send + Sync;
+    } else {
+        type Rule = dyn ValidationRule;
     }
 }
 
-impl<'a> ValidationRule<'a> for CircularDependency<'a> {
-    fn evaluate(&mut self, descriptor: &'a ServiceDescriptor, results: &mut Vec<ValidationResult>) {
-        for dependency in descriptor.dependencies() {
-            self.visited.clear();
-            self.visited.insert(descriptor.service_type());
-            self.check_dependency_graph(descriptor, dependency, results);
+macro_rules! add_rule {
+    ($($bounds:tt)+) => {
+        /// Adds the specified validation rule.
+        ///
+        /// # Arguments
+        ///
+        /// * `rule` - The [rule](ValidationRule) to add
+        pub fn add_rule<R: ValidationRule + $($bounds)+>(mut self, rule: R) -> Self {
+            self.rules.push(Box::new(rule));
+            self
         }
-    }
+    };
 }
 
-struct SingletonDependsOnScoped<'a> {
-    lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>,
-    visited: HashSet<&'a Type>,
-    queue: Vec<&'a ServiceDescriptor>,
+/// Represents the options used to validate a [ServiceCollection] and build a [ServiceProvider](crate::ServiceProvider).
+///
+/// # Remarks
+///
+/// The default options contain the built-in rules. The rules identified by `MissingRequiredType`,
+/// `CircularDependency`, and `SingletonDependsOnScoped` report errors. The rules identified by
+/// `SingletonDependsOnServiceProvider`, `AmbiguousDependency`, `ShadowedRegistration`, and `CardinalityMismatch`
+/// report warnings.
+pub struct ValidationOptions {
+    rules: Vec<Box<Rule>>,
+    severities: HashMap<String, ValidationSeverity>,
+    strict: bool,
+    validate_scopes: bool,
+    activation: EagerActivation,
+    parallel: bool,
 }
 
-impl<'a> SingletonDependsOnScoped<'a> {
-    fn new(lookup: &'a HashMap<&'a Type, Vec<&'a ServiceDescriptor>>) -> Self {
+impl ValidationOptions {
+    /// Initializes new validation options without any rules.
+    pub fn new() -> Self {
         Self {
-            lookup,
-            visited: HashSet::new(),
-            queue: Vec::new(),
+            rules: Vec::new(),
+            severities: HashMap::new(),
+            strict: false,
+            validate_scopes: cfg!(debug_assertions),
+            activation: EagerActivation::Disabled,
+            parallel: false,
         }
     }
-}
 
-impl<'a> ValidationRule<'a> for SingletonDependsOnScoped<'a> {
-    fn evaluate(&mut self, descriptor: &'a ServiceDescriptor, results: &mut Vec<ValidationResult>) {
-        if descriptor.lifetime() != ServiceLifetime::Singleton {
-            return;
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "async")] {
+            add_rule!(Send + Sync + 'static);
+        } else {
+            add_rule!('static);
         }
+    }
 
-        let mut level = "";
+    /// Removes the validation rule with the specified identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the [rule](ValidationRule) to remove
+    pub fn remove_rule(mut self, id: &str) -> Self {
+        self.rules.retain(|rule| rule.id() != id);
+        self
+    }
 
-        self.visited.clear();
-        self.queue.clear();
-        self.queue.push(descriptor);
+    /// Removes all validation rules, which disables validation.
+    pub fn clear_rules(mut self) -> Self {
+        self.rules.clear();
+        self
+    }
 
-        while let Some(current) = self.queue.pop() {
-            if !self.visited.insert(current.service_type()) {
-                continue;
-            }
+    /// Overrides the severity of the results reported by the specified validation rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The identifier of the [rule](ValidationRule) to override the severity of
+    /// * `severity` - The [severity](ValidationSeverity) of the results reported by the rule
+    ///
+    /// # Remarks
+    ///
+    /// This is typically used to treat a warning as an error so that it prevents a provider from being built.
+    pub fn severity(mut self, id: &str, severity: ValidationSeverity) -> Self {
+        self.severities.insert(String::from(id), severity);
+        self
+    }
 
-            for dependency in current.dependencies() {
-                if let Some(descriptors) = self.lookup.get(dependency.injected_type()) {
-                    for next in descriptors {
-                        self.queue.push(next);
-
-                        if next.lifetime() == ServiceLifetime::Scoped {
-                            results.push(ValidationResult::fail(format!(
-                                "The service {} has a singleton lifetime, \
-                                 but its {level}dependency '{}' has a scoped lifetime",
-                                expand_type(descriptor.implementation_type()),
-                                next.service_type().name()
-                            )));
-                        }
-                    }
-                }
-            }
+    /// Sets a value indicating whether the [ServiceProvider](crate::ServiceProvider) is strict.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True if the provider records undeclared dependencies; otherwise, false
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [build_strict_provider](ServiceCollection::build_strict_provider) for more information.
+    pub fn strict(mut self, value: bool) -> Self {
+        self.strict = value;
+        self
+    }
+
+    /// Sets a value indicating whether the [ServiceProvider](crate::ServiceProvider) verifies that scoped services are
+    /// only resolved from a scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True if the provider fails to resolve a scoped service outside of a scope; otherwise, false
+    ///
+    /// # Remarks
+    ///
+    /// Scopes are validated by default in debug builds. A scoped service resolved from the root provider behaves like
+    /// a singleton and fails with [ResolveError::Unscoped](crate::ResolveError::Unscoped). A singleton that resolves a
+    /// scoped service captures it beyond the lifetime of its scope. Validation can only detect a captive dependency
+    /// that has been declared so the provider supplied to the factory of a singleton fails to resolve any scoped
+    /// service with [ResolveError::Captive](crate::ResolveError::Captive). This includes a scoped service resolved
+    /// later through an injected [ServiceProvider](crate::ServiceProvider), [Lazy](crate::lazy::Lazy), or
+    /// [Factory](crate::factory::Factory) retained by the singleton.
+    /// A service resolved through [Owned](crate::owned::Owned) is resolved within a scope of its own, which is a
+    /// scope boundary that a singleton may safely depend on.
+    pub fn validate_scopes(mut self, value: bool) -> Self {
+        self.validate_scopes = value;
+        self
+    }
+
+    /// Sets a value indicating whether every singleton is activated when the
+    /// [ServiceProvider](crate::ServiceProvider) is built.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True to activate every singleton service when the provider is built; otherwise, false
+    ///
+    /// # Remarks
+    ///
+    /// A singleton is otherwise activated the first time it is resolved, which defers a failure to activate it until
+    /// then. Singletons are activated in dependency order so that a singleton is activated after all of the
+    /// singletons it depends on. Every singleton that fails to activate is reported by the
+    /// [error](ValidationError) returned when the provider is built.
+    pub fn activate_singletons(mut self, value: bool) -> Self {
+        self.activation = if value {
+            EagerActivation::All
+        } else {
+            EagerActivation::Disabled
+        };
+        self
+    }
+
+    /// Activates the singletons of the specified type when the [ServiceProvider](crate::ServiceProvider) is built.
+    ///
+    /// # Remarks
+    ///
+    /// The singletons that the specified singletons depend on are also activated. This function has no effect if
+    /// every singleton is [activated](Self::activate_singletons). Refer to
+    /// [activate_singletons](Self::activate_singletons) for more information.
+    #[inline]
+    pub fn activate<T: Any + ?Sized>(self) -> Self {
+        self.activate_type(Type::of::<T>())
+    }
+
+    /// Activates the singletons of the specified type and key when the [ServiceProvider](crate::ServiceProvider) is
+    /// built.
+    ///
+    /// # Remarks
+    ///
+    /// Refer to [activate](Self::activate) for more information.
+    #[inline]
+    pub fn activate_by_key<TKey, TSvc: Any + ?Sized>(self) -> Self {
+        self.activate_type(Type::keyed::<TKey, TSvc>())
+    }
+
+    /// Sets a value indicating whether independent singletons are activated in parallel.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - True to activate singletons that do not depend on each other in parallel; otherwise, false
+    ///
+    /// # Remarks
+    ///
+    /// Singletons are grouped by their position in the dependency order. The singletons within a group are activated
+    /// in parallel and each group is activated after the previous group has been activated.
+    #[cfg(feature = "async")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async")))]
+    pub fn activate_in_parallel(mut self, value: bool) -> Self {
+        self.parallel = value;
+        self
+    }
+
+    /// Gets the identifiers of the configured validation rules.
+    pub fn rules(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.id())
+    }
 
-            level = "transitive ";
+    #[inline]
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    #[inline]
+    pub(crate) fn validates_scopes(&self) -> bool {
+        self.validate_scopes
+    }
+
+    #[inline]
+    pub(crate) fn activation(&self) -> &EagerActivation {
+        &self.activation
+    }
+
+    #[inline]
+    pub(crate) fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    fn activate_type(mut self, service_type: Type) -> Self {
+        match &mut self.activation {
+            EagerActivation::All => {}
+            EagerActivation::Only(types) => types.push(service_type),
+            EagerActivation::Disabled => self.activation = EagerActivation::Only(vec![service_type]),
         }
+
+        self
+    }
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self::new()
+            .add_rule(MissingRequiredType)
+            .add_rule(CircularDependency)
+            .add_rule(SingletonDependsOnScoped)
+            .add_rule(SingletonDependsOnServiceProvider)
+            .add_rule(AmbiguousDependency)
+            .add_rule(ShadowedRegistration)
+            .add_rule(CardinalityMismatch)
     }
 }
 
@@ -210,42 +532,82 @@ impl<'a> ValidationRule<'a> for SingletonDependsOnScoped<'a> {
 /// # Arguments
 ///
 /// * `services` - The [service collection](ServiceCollection) to validate
+#[inline]
 pub fn validate(services: &ServiceCollection) -> Result<(), ValidationError> {
-    let mut lookup = HashMap::with_capacity(services.len());
+    validate_with(services, &ValidationOptions::default()).map(|_| ())
+}
 
-    for item in services.iter() {
-        let key = item.service_type();
-        let descriptors = lookup.entry(key).or_insert_with(Vec::new);
-        descriptors.push(item);
+/// Validates the specified [ServiceCollection] using the specified options.
+///
+/// # Arguments
+///
+/// * `services` - The [service collection](ServiceCollection) to validate
+/// * `options` - The [validation options](ValidationOptions) to apply
+///
+/// # Remarks
+///
+/// Validation only fails if a rule reports a result with an [error](ValidationSeverity::Error) severity. A successful
+/// validation returns any [warnings](ValidationSeverity::Warning) that were reported.
+pub fn validate_with(
+    services: &ServiceCollection,
+    options: &ValidationOptions,
+) -> Result<Vec<ValidationResult>, ValidationError> {
+    let results = evaluate(services, options);
+
+    if results
+        .iter()
+        .any(|result| result.severity() == ValidationSeverity::Error)
+    {
+        Err(ValidationError::fail(results))
+    } else {
+        Ok(results)
     }
+}
 
+// evaluates every rule and returns all of the results in the order they were reported
+pub(crate) fn evaluate(services: &ServiceCollection, options: &ValidationOptions) -> Vec<ValidationResult> {
+    let context = ValidationContext::new(services);
     let mut results = Vec::new();
-    let mut missing_type = MissingRequiredType::new(&lookup);
-    let mut circular_dep = CircularDependency::new(&lookup);
-    let mut scoped_in_singleton = SingletonDependsOnScoped::new(&lookup);
-    let mut rules: Vec<&mut dyn ValidationRule> = vec![&mut missing_type, &mut circular_dep, &mut scoped_in_singleton];
 
     for descriptor in services {
-        for rule in rules.iter_mut() {
-            rule.evaluate(descriptor, &mut results);
+        for rule in &options.rules {
+            let start = results.len();
+
+            rule.evaluate(descriptor, &context, &mut results);
+
+            if let Some(severity) = options.severities.get(rule.id()) {
+                for result in &mut results[start..] {
+                    result.severity = *severity;
+                }
+            }
         }
     }
 
-    if results.is_empty() {
-        Ok(())
-    } else {
-        Err(ValidationError::fail(results))
-    }
+    results
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        exactly_one, exactly_one_with_key, scoped, singleton, singleton_as_self, test::*, transient,
-        transient_with_key, zero_or_one, zero_or_one_with_key, Ref,
+        exactly_one, exactly_one_generic, exactly_one_with_key, exactly_one_with_value_key, scoped, scoped_generic,
+        singleton, singleton_as_self, singleton_with_value_key, test::*, transient, transient_as_self,
+        transient_factory, transient_with_key, zero_or_more_with_key, zero_or_one, zero_or_one_with_key, Ref,
+        ServiceLifetime,
     };
 
+    #[test]
+    fn validation_options_should_only_validate_scopes_by_default_in_debug_builds() {
+        // arrange
+        let options = ValidationOptions::default();
+
+        // act
+        let validated = options.validates_scopes();
+
+        // assert
+        assert_eq!(validated, cfg!(debug_assertions));
+    }
+
     #[test]
     fn validate_should_report_missing_required_type() {
         // arrange
@@ -268,6 +630,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_should_not_report_missing_required_type_for_open_generic() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add(scoped_generic::<Repository<()>>()).add(
+            transient_as_self::<UserService>()
+                .depends_on(exactly_one_generic::<Repository<User>>())
+                .from(|sp| {
+                    Ref::new(UserService {
+                        users: sp.get_required_generic::<Repository<User>>(),
+                    })
+                }),
+        );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_should_report_missing_required_type_for_closed_type_that_is_not_declared_as_generic() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add(scoped_generic::<Repository<()>>()).add(
+            transient_as_self::<UserService>()
+                .depends_on(exactly_one::<Repository<User>>())
+                .from(|sp| {
+                    Ref::new(UserService {
+                        users: sp.get_required_generic::<Repository<User>>(),
+                    })
+                }),
+        );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn validate_should_report_missing_required_value_keyed_type() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(
+                singleton_with_value_key::<dyn TestService, TestServiceImpl>("replica")
+                    .from(|_| Ref::new(TestServiceImpl::default())),
+            )
+            .add(
+                singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one_with_value_key::<dyn TestService>("primary"))
+                    .from(|sp| {
+                        Ref::new(OtherTestServiceImpl::new(
+                            sp.get_required_by_value_key::<dyn TestService>("primary"),
+                        ))
+                    }),
+            );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert_eq!(
+            &result.err().unwrap().to_string(),
+            "Service 'di::test::OtherTestServiceImpl' requires dependent service \
+             'dyn di::test::TestService' with the key 'primary', which has not be registered"
+        );
+    }
+
     #[test]
     fn validate_should_report_missing_required_keyed_type() {
         // arrange
@@ -285,7 +722,7 @@ mod tests {
                         ))
                     }),
             )
-            .add(transient_with_key::<key::Thing2, dyn Thing, Thing2>().from(|_| Ref::new(Thing2::default())));
+            .add(transient_with_key::<key::Thing2, dyn Thing, Thing2>().from(|_| Ref::new(Thing2)));
 
         // act
         let result = validate(&services);
@@ -333,7 +770,7 @@ mod tests {
                         ))
                     }),
             )
-            .add(transient_with_key::<key::Thing1, dyn Thing, Thing1>().from(|_| Ref::new(Thing1::default())));
+            .add(transient_with_key::<key::Thing1, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)));
 
         // act
         let result = validate(&services);
@@ -452,6 +889,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn validate_should_not_report_scoped_service_behind_scope_boundary_in_singleton() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(scoped::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            )
+            .add(
+                singleton::<dyn AnotherTestService, AnotherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn OtherTestService>().as_scope_boundary())
+                    .from(|sp| {
+                        Ref::new(AnotherTestServiceImpl::new(
+                            sp.create_scope().get_required::<dyn OtherTestService>(),
+                        ))
+                    }),
+            );
+
+        // act
+        let result = validate(&services);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
     #[test]
     fn validate_should_not_report_circular_dependency_when_visited_multiple_times() {
         // arrange
@@ -599,4 +1065,245 @@ mod tests {
         // assert
         assert!(result.is_err());
     }
+
+    struct TransientIsWarning;
+
+    impl ValidationRule for TransientIsWarning {
+        fn id(&self) -> &str {
+            "TransientIsWarning"
+        }
+
+        fn evaluate<'a>(
+            &self,
+            descriptor: &'a ServiceDescriptor,
+            _context: &ValidationContext<'a>,
+            results: &mut Vec<ValidationResult>,
+        ) {
+            if descriptor.lifetime() == ServiceLifetime::Transient {
+                results.push(ValidationResult::warning(
+                    self.id(),
+                    descriptor.service_type().clone(),
+                    "The service is transient",
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn validation_error_should_return_structured_results() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add(
+            singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+        );
+
+        // act
+        let error = validate(&services).err().unwrap();
+
+        // assert
+        let results = error.results();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].rule(), "MissingRequiredType");
+        assert_eq!(results[0].severity(), ValidationSeverity::Error);
+        assert_eq!(results[0].service_type(), &Type::of::<dyn OtherTestService>());
+    }
+
+    #[test]
+    fn validate_with_should_not_evaluate_removed_rule() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let options = ValidationOptions::default().remove_rule("MissingRequiredType");
+
+        services.add(
+            singleton::<dyn OtherTestService, OtherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+        );
+
+        // act
+        let result = validate_with(&services, &options);
+
+        // assert
+        assert!(result.is_ok());
+        assert_eq!(
+            options.rules().collect::<Vec<_>>(),
+            [
+                "CircularDependency",
+                "SingletonDependsOnScoped",
+                "SingletonDependsOnServiceProvider",
+                "AmbiguousDependency",
+                "ShadowedRegistration",
+                "CardinalityMismatch"
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_with_should_not_fail_when_results_are_warnings() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let options = ValidationOptions::default().add_rule(TransientIsWarning);
+
+        services.add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())));
+
+        // act
+        let result = validate_with(&services, &options);
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_with_should_report_warnings_separately_when_validation_fails() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let options = ValidationOptions::new()
+            .add_rule(TransientIsWarning)
+            .add_rule(MissingRequiredType);
+
+        services.add(
+            transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+        );
+
+        // act
+        let error = validate_with(&services, &options).err().unwrap();
+
+        // assert
+        assert_eq!(error.results().len(), 1);
+        assert_eq!(error.results()[0].severity(), ValidationSeverity::Error);
+        assert_eq!(error.warnings().len(), 1);
+        assert_eq!(error.warnings()[0].severity(), ValidationSeverity::Warning);
+        assert_eq!(error.to_string(), error.results()[0].message());
+    }
+
+    #[test]
+    fn validate_with_should_not_fail_when_rules_are_cleared() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services.add(
+            singleton::<dyn TestService, TestCircularDepImpl>()
+                .depends_on(exactly_one::<dyn TestService>())
+                .from(|sp| Ref::new(TestCircularDepImpl::new(sp.get_required::<dyn TestService>()))),
+        );
+
+        // act
+        let result = validate_with(&services, &ValidationOptions::default().clear_rules());
+
+        // assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_with_should_warn_when_single_dependency_is_ambiguous() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(transient::<dyn TestService, TestService2Impl>().from(|_| Ref::new(TestService2Impl::default())))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(exactly_one::<dyn TestService>())
+                    .from(|sp| Ref::new(OtherTestServiceImpl::new(sp.get_required::<dyn TestService>()))),
+            );
+
+        // act
+        let warnings = validate_with(&services, &ValidationOptions::default()).unwrap();
+
+        // assert
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].rule(), "AmbiguousDependency");
+        assert_eq!(warnings[0].severity(), ValidationSeverity::Warning);
+        assert_eq!(warnings[0].service_type(), &Type::of::<dyn OtherTestService>());
+        assert_eq!(
+            warnings[0].message(),
+            "Service 'di::test::OtherTestServiceImpl' requires a single dependent service \
+             'dyn di::test::TestService', but 2 services have been registered"
+        );
+    }
+
+    #[test]
+    fn validate_with_should_warn_when_registration_is_shadowed() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(transient::<dyn TestService, TestService2Impl>().from(|_| Ref::new(TestService2Impl::default())))
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())));
+
+        // act
+        let warnings = validate_with(&services, &ValidationOptions::default()).unwrap();
+
+        // assert
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].rule(), "ShadowedRegistration");
+        assert_eq!(warnings[0].service_type(), &Type::of::<dyn TestService>());
+    }
+
+    #[test]
+    fn validate_with_should_not_warn_when_factories_are_registered_more_than_once() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(transient_factory(|_| Ref::new(TestServiceImpl::default())))
+            .add(transient_factory(|_| Ref::new(TestServiceImpl { value: 1 })));
+
+        // act
+        let warnings = validate_with(&services, &ValidationOptions::default()).unwrap();
+
+        // assert
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn validate_with_should_warn_when_keyed_dependency_has_only_one_registration() {
+        // arrange
+        let mut services = ServiceCollection::new();
+
+        services
+            .add(transient_with_key::<key::Thing1, dyn Thing, Thing1>().from(|_| Ref::new(Thing1)))
+            .add(
+                transient::<dyn OtherTestService, OtherTestServiceImpl>()
+                    .depends_on(zero_or_more_with_key::<key::Thing1, dyn Thing>())
+                    .from(|_| Ref::new(OtherTestServiceImpl::new(Ref::new(TestServiceImpl::default())))),
+            );
+
+        // act
+        let warnings = validate_with(&services, &ValidationOptions::default()).unwrap();
+
+        // assert
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].rule(), "CardinalityMismatch");
+        assert_eq!(warnings[0].service_type(), &Type::of::<dyn OtherTestService>());
+    }
+
+    #[test]
+    fn validate_with_should_fail_when_warning_severity_is_overridden() {
+        // arrange
+        let mut services = ServiceCollection::new();
+        let options = ValidationOptions::default().severity("ShadowedRegistration", ValidationSeverity::Error);
+
+        services
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())))
+            .add(transient::<dyn TestService, TestServiceImpl>().from(|_| Ref::new(TestServiceImpl::default())));
+
+        // act
+        let error = validate_with(&services, &options).err().unwrap();
+
+        // assert
+        assert_eq!(
+            error.to_string(),
+            "Service 'dyn di::test::TestService' has already been registered with implementation \
+             'di::test::TestServiceImpl'"
+        );
+        assert_eq!(error.results()[0].severity(), ValidationSeverity::Error);
+    }
 }
@@ -0,0 +1,304 @@
+use super::{ValidationContext, ValidationResult, ValidationRule};
+use crate::r#type::expand_type;
+use crate::{ServiceCardinality, ServiceDependency, ServiceDescriptor, ServiceLifetime, ServiceProvider, Type};
+use std::collections::HashSet;
+
+pub(crate) struct MissingRequiredType;
+
+impl ValidationRule for MissingRequiredType {
+    fn id(&self) -> &str {
+        "MissingRequiredType"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        for dependency in descriptor.dependencies() {
+            if dependency.cardinality() == ServiceCardinality::ExactlyOne
+                && !dependency.is_provided()
+                && context.resolve(dependency).is_empty()
+            {
+                results.push(ValidationResult::error(
+                    self.id(),
+                    descriptor.service_type().clone(),
+                    format!(
+                        "Service '{}' requires dependent service {}, which has not be registered",
+                        descriptor.implementation_type().name(),
+                        expand_type(dependency.injected_type())
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+pub(crate) struct CircularDependency;
+
+impl CircularDependency {
+    fn check_dependency_graph<'a>(
+        &self,
+        root: &'a ServiceDescriptor,
+        dependency: &'a ServiceDependency,
+        context: &ValidationContext<'a>,
+        visited: &mut HashSet<&'a Type>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        let mut queue = vec![dependency];
+
+        while let Some(current) = queue.pop() {
+            for descriptor in context.get(current.injected_type()) {
+                if visited.insert(descriptor.service_type()) {
+                    queue.extend(descriptor.dependencies());
+                }
+
+                if descriptor.service_type() == root.service_type() {
+                    results.push(ValidationResult::error(
+                        self.id(),
+                        root.service_type().clone(),
+                        format!(
+                            "A circular dependency was detected for service {} on service '{}'",
+                            expand_type(descriptor.service_type()),
+                            root.implementation_type().name()
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl ValidationRule for CircularDependency {
+    fn id(&self) -> &str {
+        "CircularDependency"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        let mut visited = HashSet::new();
+
+        for dependency in descriptor.dependencies() {
+            visited.clear();
+            visited.insert(descriptor.service_type());
+            self.check_dependency_graph(descriptor, dependency, context, &mut visited, results);
+        }
+    }
+}
+
+pub(crate) struct SingletonDependsOnScoped;
+
+impl ValidationRule for SingletonDependsOnScoped {
+    fn id(&self) -> &str {
+        "SingletonDependsOnScoped"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        if descriptor.lifetime() != ServiceLifetime::Singleton {
+            return;
+        }
+
+        let mut level = "";
+        let mut visited = HashSet::new();
+        let mut queue = vec![descriptor];
+
+        while let Some(current) = queue.pop() {
+            if !visited.insert(current.service_type()) {
+                continue;
+            }
+
+            // the scoped services behind a scope boundary belong to its own scope
+            for dependency in current.dependencies().iter().filter(|d| !d.is_scope_boundary()) {
+                for next in context.resolve(dependency) {
+                    queue.push(next);
+
+                    if next.lifetime() == ServiceLifetime::Scoped {
+                        results.push(ValidationResult::error(
+                            self.id(),
+                            descriptor.service_type().clone(),
+                            format!(
+                                "The service {} has a singleton lifetime, \
+                                 but its {level}dependency '{}' has a scoped lifetime",
+                                expand_type(descriptor.implementation_type()),
+                                next.service_type().name()
+                            ),
+                        ));
+                    }
+                }
+            }
+
+            level = "transitive ";
+        }
+    }
+}
+
+pub(crate) struct SingletonDependsOnServiceProvider;
+
+impl ValidationRule for SingletonDependsOnServiceProvider {
+    fn id(&self) -> &str {
+        "SingletonDependsOnServiceProvider"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        if descriptor.lifetime() != ServiceLifetime::Singleton {
+            return;
+        }
+
+        let provider = Type::of::<ServiceProvider>();
+        let mut visited = HashSet::new();
+        let mut queue = vec![descriptor];
+
+        while let Some(current) = queue.pop() {
+            if !visited.insert(current.service_type()) {
+                continue;
+            }
+
+            for dependency in current.dependencies() {
+                if *dependency.injected_type() == provider {
+                    results.push(ValidationResult::warning(
+                        self.id(),
+                        descriptor.service_type().clone(),
+                        format!(
+                            "The service {} has a singleton lifetime, but '{}' depends on the service provider, \
+                             which can capture a scoped service",
+                            expand_type(descriptor.implementation_type()),
+                            current.implementation_type().name()
+                        ),
+                    ));
+                    return;
+                }
+
+                queue.extend(context.resolve(dependency));
+            }
+        }
+    }
+}
+
+pub(crate) struct AmbiguousDependency;
+
+impl ValidationRule for AmbiguousDependency {
+    fn id(&self) -> &str {
+        "AmbiguousDependency"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        for dependency in descriptor.dependencies() {
+            if dependency.cardinality() == ServiceCardinality::ZeroOrMore {
+                continue;
+            }
+
+            let count = context.get(dependency.injected_type()).len();
+
+            // a single resolution silently uses the last registration
+            if count > 1 {
+                results.push(ValidationResult::warning(
+                    self.id(),
+                    descriptor.service_type().clone(),
+                    format!(
+                        "Service '{}' requires a single dependent service {}, but {} services have been registered",
+                        descriptor.implementation_type().name(),
+                        expand_type(dependency.injected_type()),
+                        count
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+pub(crate) struct ShadowedRegistration;
+
+impl ValidationRule for ShadowedRegistration {
+    fn id(&self) -> &str {
+        "ShadowedRegistration"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        // every factory function has the same implementation type so
+        // registrations that use one cannot be distinguished
+        if descriptor.implementation_type().is_factory() {
+            return;
+        }
+
+        let shadowed = context
+            .get(descriptor.service_type())
+            .iter()
+            .take_while(|other| !std::ptr::eq(**other, descriptor))
+            .any(|other| other.implementation_type() == descriptor.implementation_type());
+
+        if shadowed {
+            results.push(ValidationResult::warning(
+                self.id(),
+                descriptor.service_type().clone(),
+                format!(
+                    "Service {} has already been registered with implementation '{}'",
+                    expand_type(descriptor.service_type()),
+                    descriptor.implementation_type().name()
+                ),
+            ));
+        }
+    }
+}
+
+pub(crate) struct CardinalityMismatch;
+
+impl ValidationRule for CardinalityMismatch {
+    fn id(&self) -> &str {
+        "CardinalityMismatch"
+    }
+
+    fn evaluate<'a>(
+        &self,
+        descriptor: &'a ServiceDescriptor,
+        context: &ValidationContext<'a>,
+        results: &mut Vec<ValidationResult>,
+    ) {
+        for dependency in descriptor.dependencies() {
+            let injected_type = dependency.injected_type();
+
+            if dependency.cardinality() != ServiceCardinality::ZeroOrMore
+                || Type::deconstruct(injected_type).1.is_none()
+            {
+                continue;
+            }
+
+            // a key typically identifies a single service
+            if context.get(injected_type).len() == 1 {
+                results.push(ValidationResult::warning(
+                    self.id(),
+                    descriptor.service_type().clone(),
+                    format!(
+                        "Service '{}' requires zero or more dependent services {}, but only one service has been registered",
+                        descriptor.implementation_type().name(),
+                        expand_type(injected_type)
+                    ),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use di::*;
+
+// a web service typically creates a scope per request and resolves a handful
+// of services from it. the number of registrations should not matter.
+
+#[derive(Default)]
+struct Service(usize);
+
+#[derive(Default)]
+struct Request;
+
+fn new_provider(registrations: usize) -> ServiceProvider {
+    let mut services = ServiceCollection::new();
+
+    for i in 0..registrations {
+        let service = move |_: &ServiceProvider| Ref::new(Service(i));
+
+        match i % 3 {
+            0 => services.add(singleton_with_value_key::<Service, Service>(i).from(service)),
+            1 => services.add(scoped_with_value_key::<Service, Service>(i).from(service)),
+            _ => services.add(transient_with_value_key::<Service, Service>(i).from(service)),
+        };
+    }
+
+    services
+        .add(scoped_factory(|_| Ref::new(Request)))
+        .build_provider()
+        .unwrap()
+}
+
+fn create_scope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("create_scope");
+
+    for registrations in [10, 100, 1000] {
+        let provider = new_provider(registrations);
+
+        group.bench_with_input(BenchmarkId::from_parameter(registrations), &provider, |b, provider| {
+            b.iter(|| black_box(provider.create_scope()))
+        });
+    }
+
+    group.finish();
+}
+
+fn resolve_from_scope(c: &mut Criterion) {
+    let mut group = c.benchmark_group("resolve_from_scope");
+
+    for registrations in [10, 100, 1000] {
+        let provider = new_provider(registrations);
+
+        group.bench_with_input(BenchmarkId::from_parameter(registrations), &provider, |b, provider| {
+            b.iter(|| {
+                let scope = provider.create_scope();
+                let request = scope.get_required::<Request>();
+                let service = scope.get_required_by_value_key::<Service>(&1usize);
+                black_box((request, service.0))
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, create_scope, resolve_from_scope);
+criterion_main!(benches);
@@ -3,6 +3,7 @@
 use crate::traits::*;
 use di::*;
 use std::env::set_var;
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
 #[injectable]
 struct A;
@@ -44,12 +45,17 @@ struct Thing3 {
 // use: invalid lifetime
 impl Thing for Thing3 {}
 
-impl ToString for Thing3 {
-    fn to_string(&self) -> String {
-        std::any::type_name::<Self>().to_string()
+impl Display for Thing3 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(std::any::type_name::<Self>())
     }
 }
 
+#[injectable]
+struct Locator {
+    services: ServiceProvider, // use: always available
+}
+
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
 trait Logger {}
 
@@ -58,6 +64,14 @@ struct G {
     loggers: Vec<Ref<dyn Logger>>, // use: empty list
 }
 
+#[injectable]
+struct Session;
+
+#[injectable]
+struct Worker {
+    session: owned::Owned<Ref<Session>>, // use: scope boundary
+}
+
 fn new_service_collection() -> ServiceCollection {
     let mut services = ServiceCollection::new();
 
@@ -153,6 +167,66 @@ const TEXT_TERMINAL: &str =
  └ [38;2;75;154;214mdyn[0m [38;2;158;211;163mmore_di_tests::traits::Thing[0m → [38;2;78;201;176mmore_di_tests::format::Thing3[0m [38;2;118;118;118m[Scoped][0m\n  \
    └ [38;2;78;201;176mmore_di_tests::format::A[0m → [38;2;78;201;176mmore_di_tests::format::A[0m [38;2;118;118;118m[Singleton][0m\n";
 
+const DOT: &str = r##"digraph services {
+    node [shape=box, fontname="monospace"];
+    edge [fontname="monospace", fontsize=10];
+    s0 [label="more_di_tests::format::A\nmore_di_tests::format::A\n[Singleton]"];
+    s1 [label="more_di_tests::format::B\nmore_di_tests::format::B\n[Singleton]"];
+    s2 [label="more_di_tests::format::C\nmore_di_tests::format::C\n[Transient]"];
+    s3 [label="more_di_tests::format::D\nmore_di_tests::format::D\n[Singleton]"];
+    s4 [label="more_di_tests::format::E\nmore_di_tests::format::E\n[Transient]"];
+    s5 [label="more_di_tests::format::F\nmore_di_tests::format::F\n[Transient]"];
+    s6 [label="more_di_tests::format::G\nmore_di_tests::format::G\n[Transient]"];
+    s7 [label="dyn more_di_tests::traits::Thing\nmore_di_tests::traits::Thing2\n[Transient]"];
+    s8 [label="dyn more_di_tests::traits::Thing\nmore_di_tests::format::Thing3\n[Scoped]"];
+    m9 [label="more_di_tests::traits::CatInTheHat\n▲ Missing", style=dashed, color="#c19c00", fontcolor="#c19c00"];
+    m10 [label="more_di_tests::traits::Thing1\n‼ Missing", style=dashed, color="#e74856", fontcolor="#e74856"];
+    m11 [label="dyn more_di_tests::format::Logger\n▲ Count: 0", style=dashed, color="#c19c00", fontcolor="#c19c00"];
+    s1 -> s0 [label="ExactlyOne"];
+    s2 -> s1 [label="ExactlyOne"];
+    s3 -> s0 [label="ExactlyOne"];
+    s3 -> s2 [label="ZeroOrOne"];
+    s3 -> m9 [label="ZeroOrOne"];
+    s3 -> m10 [label="ExactlyOne"];
+    s3 -> s7 [label="ZeroOrMore"];
+    s3 -> s8 [label="ZeroOrMore ⧗", color="#e74856", fontcolor="#e74856"];
+    s4 -> s5 [label="ExactlyOne ♺", color="#e74856", fontcolor="#e74856"];
+    s5 -> s4 [label="ExactlyOne ♺", color="#e74856", fontcolor="#e74856"];
+    s6 -> m11 [label="ZeroOrMore"];
+    s8 -> s0 [label="ExactlyOne"];
+}
+"##;
+
+const MERMAID: &str = r##"flowchart LR
+    s0["more_di_tests::format::A<br/>more_di_tests::format::A<br/>[Singleton]"]
+    s1["more_di_tests::format::B<br/>more_di_tests::format::B<br/>[Singleton]"]
+    s2["more_di_tests::format::C<br/>more_di_tests::format::C<br/>[Transient]"]
+    s3["more_di_tests::format::D<br/>more_di_tests::format::D<br/>[Singleton]"]
+    s4["more_di_tests::format::E<br/>more_di_tests::format::E<br/>[Transient]"]
+    s5["more_di_tests::format::F<br/>more_di_tests::format::F<br/>[Transient]"]
+    s6["more_di_tests::format::G<br/>more_di_tests::format::G<br/>[Transient]"]
+    s7["dyn more_di_tests::traits::Thing<br/>more_di_tests::traits::Thing2<br/>[Transient]"]
+    s8["dyn more_di_tests::traits::Thing<br/>more_di_tests::format::Thing3<br/>[Scoped]"]
+    m9["more_di_tests::traits::CatInTheHat<br/>▲ Missing"]:::warning
+    m10["more_di_tests::traits::Thing1<br/>‼ Missing"]:::error
+    m11["dyn more_di_tests::format::Logger<br/>▲ Count: 0"]:::warning
+    s1 -->|"ExactlyOne"| s0
+    s2 -->|"ExactlyOne"| s1
+    s3 -->|"ExactlyOne"| s0
+    s3 -->|"ZeroOrOne"| s2
+    s3 -->|"ZeroOrOne"| m9
+    s3 -->|"ExactlyOne"| m10
+    s3 -->|"ZeroOrMore"| s7
+    s3 -->|"ZeroOrMore ⧗"| s8
+    s4 -->|"ExactlyOne ♺"| s5
+    s5 -->|"ExactlyOne ♺"| s4
+    s6 -->|"ZeroOrMore"| m11
+    s8 -->|"ExactlyOne"| s0
+    linkStyle 7,8,9 stroke:#e74856,color:#e74856
+    classDef error stroke:#e74856,color:#e74856,stroke-dasharray:4
+    classDef warning stroke:#c19c00,color:#c19c00,stroke-dasharray:4
+"##;
+
 fn force_color_support() {
     set_var("CLICOLOR_FORCE", "1");
     set_var("COLORTERM", "truecolor");
@@ -196,3 +270,312 @@ fn alt_display_should_format_service_collection() {
     // assert
     assert_eq!(output, TEXT_TERMINAL);
 }
+
+#[test]
+fn display_should_format_service_with_value_key() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(A::singleton().with_value_key("primary"))
+        .add(singleton_with_value_key::<B, B>(42).from(|sp| {
+            Ref::new(B {
+                a: sp.get_required_by_value_key::<A>("primary"),
+            })
+        }));
+
+    // act
+    let output = format!("{services}");
+
+    // assert
+    assert_eq!(
+        output,
+        "┌ more_di_tests::format::A [⚿ primary] → more_di_tests::format::A [Singleton]\n\
+         │\n\
+         └ more_di_tests::format::B [⚿ 42] → more_di_tests::format::B [Singleton]\n"
+    );
+}
+
+#[test]
+fn to_dot_should_export_service_collection() {
+    // arrange
+    let services = new_service_collection();
+
+    // act
+    let output = services.to_dot();
+
+    // assert
+    assert_eq!(output, DOT);
+}
+
+#[test]
+fn to_mermaid_should_export_service_collection() {
+    // arrange
+    let services = new_service_collection();
+
+    // act
+    let output = services.to_mermaid();
+
+    // assert
+    assert_eq!(output, MERMAID);
+}
+
+#[test]
+fn exports_should_not_report_injected_service_provider_as_missing() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(Locator::transient());
+
+    // act
+    let outputs = [format!("{services}"), services.to_dot(), services.to_mermaid()];
+
+    // assert
+    assert!(validate(&services).is_ok());
+    assert!(outputs.iter().all(|output| !output.contains("Missing")));
+}
+
+#[test]
+fn exports_should_not_report_scoped_service_behind_scope_boundary_in_singleton() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(Session::scoped()).add(Worker::singleton());
+
+    // act
+    let outputs = [format!("{services}"), services.to_dot(), services.to_mermaid()];
+
+    // assert
+    assert!(validate(&services).is_ok());
+    assert!(outputs.iter().all(|output| !output.contains('⧗')));
+}
+
+struct AsciiRenderer(String);
+
+impl fmt::Renderer for AsciiRenderer {
+    fn write_str(&mut self, text: &str) -> FmtResult {
+        self.0.push_str(&text.replace('→', "->"));
+        Ok(())
+    }
+
+    fn branch(&mut self, ch: char) -> FmtResult {
+        self.0.push(match ch {
+            '│' => '|',
+            '└' => '`',
+            _ => '+',
+        });
+        Ok(())
+    }
+}
+
+struct HtmlRenderer(String);
+
+impl fmt::Renderer for HtmlRenderer {
+    fn write_str(&mut self, text: &str) -> FmtResult {
+        self.0.push_str(&text.replace('<', "&lt;").replace('>', "&gt;"));
+        Ok(())
+    }
+
+    fn error(&mut self, text: &str) -> FmtResult {
+        self.0.push_str("<span class=\"error\">");
+        self.write_str(text)?;
+        self.0.push_str("</span>");
+        Ok(())
+    }
+}
+
+fn new_chain() -> ServiceCollection {
+    let mut services = ServiceCollection::new();
+    services.add(A::transient()).add(B::transient()).add(C::transient());
+    services
+}
+
+#[test]
+fn render_should_use_custom_renderer() {
+    // arrange
+    let services = new_chain();
+    let mut renderer = AsciiRenderer(String::new());
+
+    // act
+    services.render(&mut renderer).unwrap();
+
+    // assert
+    assert_eq!(
+        renderer.0,
+        concat!(
+            "+ more_di_tests::format::A -> more_di_tests::format::A [Transient]\n",
+            "|\n",
+            "+ more_di_tests::format::B -> more_di_tests::format::B [Transient]\n",
+            "| ` more_di_tests::format::A -> more_di_tests::format::A [Transient]\n",
+            "|\n",
+            "` more_di_tests::format::C -> more_di_tests::format::C [Transient]\n",
+            "  ` more_di_tests::format::B -> more_di_tests::format::B [Transient]\n",
+            "    ` more_di_tests::format::A -> more_di_tests::format::A [Transient]\n",
+        )
+    );
+}
+
+#[test]
+fn render_should_classify_output() {
+    // arrange
+    let mut services = ServiceCollection::new();
+    let mut renderer = HtmlRenderer(String::new());
+
+    services.add(B::transient());
+
+    // act
+    services.render(&mut renderer).unwrap();
+
+    // assert
+    assert_eq!(
+        renderer.0,
+        concat!(
+            "└ more_di_tests::format::B → more_di_tests::format::B [Transient]\n",
+            "  └ more_di_tests::format::A → <span class=\"error\">‼ Missing</span>\n",
+        )
+    );
+}
+
+#[test]
+fn render_with_should_limit_depth() {
+    // arrange
+    let services = new_chain();
+    let options = fmt::RenderOptions::new().max_depth(1);
+    let mut renderer = fmt::text::Renderer::new(String::new());
+
+    // act
+    services.render_with(&mut renderer, &options).unwrap();
+
+    // assert
+    assert_eq!(
+        renderer.into_inner(),
+        concat!(
+            "┌ more_di_tests::format::A → more_di_tests::format::A [Transient]\n",
+            "│\n",
+            "├ more_di_tests::format::B → more_di_tests::format::B [Transient]\n",
+            "│ └ more_di_tests::format::A → more_di_tests::format::A [Transient]\n",
+            "│\n",
+            "└ more_di_tests::format::C → more_di_tests::format::C [Transient]\n",
+            "  └ more_di_tests::format::B → more_di_tests::format::B [Transient] …\n",
+        )
+    );
+}
+
+#[test]
+fn render_with_should_collapse_repeated_dependencies() {
+    // arrange
+    let services = new_chain();
+    let options = fmt::RenderOptions::new().collapse(true);
+    let mut renderer = fmt::text::Renderer::new(String::new());
+
+    // act
+    services.render_with(&mut renderer, &options).unwrap();
+
+    // assert
+    assert_eq!(
+        renderer.into_inner(),
+        concat!(
+            "┌ more_di_tests::format::A → more_di_tests::format::A [Transient]\n",
+            "│\n",
+            "├ more_di_tests::format::B → more_di_tests::format::B [Transient]\n",
+            "│ └ more_di_tests::format::A → more_di_tests::format::A [Transient]\n",
+            "│\n",
+            "└ more_di_tests::format::C → more_di_tests::format::C [Transient]\n",
+            "  └ more_di_tests::format::B → more_di_tests::format::B [Transient] ↑\n",
+        )
+    );
+}
+
+#[test]
+fn render_with_should_filter_services() {
+    // arrange
+    let services = new_chain();
+    let options = fmt::RenderOptions::new().filter(|ty| *ty == Type::of::<C>());
+    let mut renderer = fmt::text::Renderer::new(String::new());
+
+    // act
+    services.render_with(&mut renderer, &options).unwrap();
+
+    // assert
+    assert_eq!(
+        renderer.into_inner(),
+        concat!(
+            "└ more_di_tests::format::C → more_di_tests::format::C [Transient]\n",
+            "  └ more_di_tests::format::B → more_di_tests::format::B [Transient]\n",
+            "    └ more_di_tests::format::A → more_di_tests::format::A [Transient]\n",
+        )
+    );
+}
+
+#[test]
+fn explain_should_render_effective_lifetimes() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(A::transient()).add(B::transient()).add(C::singleton());
+
+    // act
+    let output = services.explain::<C>().to_string();
+
+    // assert
+    assert_eq!(
+        output,
+        concat!(
+            "└ more_di_tests::format::C → more_di_tests::format::C [Singleton]\n",
+            "  └ more_di_tests::format::B → more_di_tests::format::B [Transient, effectively Singleton]\n",
+            "    └ more_di_tests::format::A → more_di_tests::format::A [Transient, effectively Singleton]\n",
+        )
+    );
+}
+
+#[test]
+fn explain_should_render_validation_errors() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(A::transient()).add(B::scoped()).add(C::singleton());
+
+    // act
+    let output = services.explain::<C>().to_string();
+
+    // assert
+    assert_eq!(
+        output,
+        concat!(
+            "└ more_di_tests::format::C → more_di_tests::format::C [Singleton]\n",
+            "  └ more_di_tests::format::B → ⧗ more_di_tests::format::B [Scoped]\n",
+            "    └ more_di_tests::format::A → more_di_tests::format::A [Transient, effectively Singleton]\n",
+            "\n",
+            "‼ SingletonDependsOnScoped: The service 'more_di_tests::format::C' has a singleton lifetime, but its dependency 'more_di_tests::format::B' has a scoped lifetime\n",
+        )
+    );
+}
+
+#[test]
+fn explain_should_render_missing_service() {
+    // arrange
+    let services = new_chain();
+
+    // act
+    let output = services.explain::<G>().to_string();
+
+    // assert
+    assert_eq!(output, "└ more_di_tests::format::G → ‼ Missing\n");
+}
+
+#[test]
+fn explain_by_key_should_render_keyed_service() {
+    // arrange
+    let mut services = new_chain();
+
+    services.add(A::singleton().with_key::<Thing1>());
+
+    // act
+    let output = services.explain_by_key::<Thing1, A>().to_string();
+
+    // assert
+    assert_eq!(
+        output,
+        "└ more_di_tests::format::A [⚿ more_di_tests::traits::Thing1] → more_di_tests::format::A [Singleton]\n"
+    );
+}
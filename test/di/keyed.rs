@@ -5,7 +5,7 @@
 #![allow(dead_code)]
 
 use cfg_if::cfg_if;
-use di::{injectable, lazy::Lazy, KeyedRef, KeyedRefMut};
+use di::{factory::Factory, injectable, lazy::Lazy, KeyedRef, KeyedRefMut};
 
 cfg_if! {
     if #[cfg(feature = "async")] {
@@ -148,6 +148,22 @@ impl KeyedStructLazyImpl {
     }
 }
 
+#[injectable]
+pub struct KeyedStructFactory {
+    pub dep: Factory<KeyedRef<key::Key2, KeyedDep>>,
+}
+
+pub struct KeyedStructFactoryImpl {
+    dep: Factory<KeyedRef<key::Key2, KeyedDep>>,
+}
+
+#[injectable]
+impl KeyedStructFactoryImpl {
+    fn new(dep: Factory<KeyedRef<key::Key2, KeyedDep>>) -> Self {
+        Self { dep }
+    }
+}
+
 #[injectable]
 pub struct KeyedStructMut {
     pub dep: KeyedRefMut<key::Key3, KeyedDep>,
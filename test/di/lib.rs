@@ -8,6 +8,7 @@
 mod containers;
 mod format;
 mod keyed;
+mod manifest;
 mod mutable;
 mod scenarios;
 mod structs;
@@ -0,0 +1,135 @@
+use crate::traits::*;
+use di::*;
+
+#[allow(dead_code)]
+#[injectable]
+struct Locator {
+    services: ServiceProvider,
+}
+
+#[test]
+fn manifest_should_describe_registered_services() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(FooImpl::transient())
+        .add(BarImpl::singleton().with_key::<key::Thing1>());
+
+    // act
+    let manifest = services.manifest();
+
+    // assert
+    assert_eq!(manifest.version, Manifest::VERSION);
+    assert_eq!(
+        manifest.services,
+        vec![
+            ServiceRecord {
+                service_type: String::from("dyn more_di_tests::traits::Foo"),
+                implementation_type: String::from("more_di_tests::traits::FooImpl"),
+                key: None,
+                lifetime: ServiceLifetime::Transient,
+                dependencies: vec![DependencyRecord {
+                    injected_type: String::from("dyn more_di_tests::traits::Bar"),
+                    key: None,
+                    cardinality: ServiceCardinality::ExactlyOne,
+                }],
+            },
+            ServiceRecord {
+                service_type: String::from("dyn more_di_tests::traits::Bar"),
+                implementation_type: String::from("more_di_tests::traits::BarImpl"),
+                key: Some(String::from("more_di_tests::traits::key::Thing1")),
+                lifetime: ServiceLifetime::Singleton,
+                dependencies: Vec::new(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn manifest_should_include_validation_results() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(FooImpl::transient());
+
+    // act
+    let manifest = services.manifest();
+
+    // assert
+    assert_eq!(manifest.validation.len(), 1);
+    assert_eq!(manifest.validation[0].rule, "MissingRequiredType");
+    assert_eq!(manifest.validation[0].severity, ValidationSeverity::Error);
+    assert_eq!(manifest.validation[0].service_type, "dyn more_di_tests::traits::Foo");
+    assert_eq!(manifest.validation[0].key, None);
+}
+
+#[test]
+fn manifest_should_not_record_injected_service_provider() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(Locator::transient());
+
+    // act
+    let manifest = services.manifest();
+
+    // assert
+    assert!(manifest.services[0].dependencies.is_empty());
+    assert!(manifest.validation.is_empty());
+}
+
+#[test]
+fn to_json_should_export_manifest() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(FooImpl::transient()).add(BarImpl::singleton());
+
+    // act
+    let json = services.to_json();
+
+    // assert
+    assert_eq!(json, JSON);
+}
+
+#[test]
+fn to_json_should_round_trip_manifest() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(FooImpl::transient());
+
+    // act
+    let manifest: Manifest = serde_json::from_str(&services.to_json()).unwrap();
+
+    // assert
+    assert_eq!(manifest, services.manifest());
+}
+
+const JSON: &str = r#"{
+  "version": 1,
+  "services": [
+    {
+      "service_type": "dyn more_di_tests::traits::Foo",
+      "implementation_type": "more_di_tests::traits::FooImpl",
+      "key": null,
+      "lifetime": "Transient",
+      "dependencies": [
+        {
+          "injected_type": "dyn more_di_tests::traits::Bar",
+          "key": null,
+          "cardinality": "ExactlyOne"
+        }
+      ]
+    },
+    {
+      "service_type": "dyn more_di_tests::traits::Bar",
+      "implementation_type": "more_di_tests::traits::BarImpl",
+      "key": null,
+      "lifetime": "Singleton",
+      "dependencies": []
+    }
+  ],
+  "validation": []
+}"#;
@@ -5,7 +5,7 @@
 #![allow(dead_code)]
 
 use cfg_if::cfg_if;
-use di::{injectable, lazy::Lazy, Ref, RefMut};
+use di::{factory::Factory, injectable, lazy::Lazy, Ref, RefMut};
 
 cfg_if! {
     if #[cfg(feature = "async")] {
@@ -141,3 +141,19 @@ impl MutStructLazyImpl {
         Self { dep }
     }
 }
+
+#[injectable]
+pub struct MutStructFactory {
+    pub dep: Factory<RefMut<MutDep>>,
+}
+
+pub struct MutStructFactoryImpl {
+    dep: Factory<RefMut<MutDep>>,
+}
+
+#[injectable]
+impl MutStructFactoryImpl {
+    fn new(dep: Factory<RefMut<MutDep>>) -> Self {
+        Self { dep }
+    }
+}
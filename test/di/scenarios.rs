@@ -64,7 +64,7 @@ fn inject_should_implement_struct_for_self_with_dependency() {
 }
 
 #[test]
-#[allow(clippy::vtable_address_comparisons)]
+#[allow(ambiguous_wide_pointer_comparisons)]
 fn inject_should_clone_service_provider_and_return_same_singleton() {
     // arrange
     let provider = ServiceCollection::new()
@@ -84,7 +84,7 @@ fn inject_should_clone_service_provider_and_return_same_singleton() {
 }
 
 #[test]
-#[allow(clippy::vtable_address_comparisons)]
+#[allow(ambiguous_wide_pointer_comparisons)]
 fn inject_should_clone_service_provider_and_return_different_scoped_instance() {
     // arrange
     let provider = ServiceCollection::new()
@@ -92,7 +92,8 @@ fn inject_should_clone_service_provider_and_return_different_scoped_instance() {
         .add(traits::BarImpl::transient())
         .add(containers::ScopedContainer::transient())
         .build_provider()
-        .unwrap();
+        .unwrap()
+        .create_scope();
     let container = provider.get_required::<containers::ScopedContainer>();
 
     // act
@@ -228,6 +229,88 @@ fn inject_should_implement_many_lazy_trait() {
     assert_eq!("Success!", foo.echo())
 }
 
+#[test]
+#[allow(ambiguous_wide_pointer_comparisons)]
+fn inject_should_implement_factory_struct() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::transient())
+        .add(traits::FactoryFoo::singleton())
+        .build_provider()
+        .unwrap();
+    let foo = provider.get_required::<traits::FactoryFoo>();
+
+    // act
+    let bar1 = foo.bar.create();
+    let bar2 = foo.bar.create();
+
+    // assert
+    assert_eq!("Success!", bar1.echo());
+    assert!(!Ref::ptr_eq(&bar1, &bar2));
+}
+
+#[test]
+#[allow(ambiguous_wide_pointer_comparisons)]
+fn inject_should_implement_owned_struct() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::scoped())
+        .add(traits::OwnedFoo::singleton())
+        .build_provider()
+        .unwrap();
+    let scope = provider.create_scope();
+
+    // act
+    let foo = scope.get_required::<traits::OwnedFoo>();
+
+    // assert
+    assert_eq!("Success!", foo.bar.echo());
+    assert!(Ref::ptr_eq(&foo.bar, &foo.bar.scope().get_required::<dyn Bar>()));
+    assert!(!Ref::ptr_eq(&foo.bar, &scope.get_required::<dyn Bar>()));
+}
+
+#[test]
+fn inject_should_implement_assisted_factory_for_runtime_arguments() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::singleton())
+        .add(traits::OrderWorkflow::transient())
+        .add(traits::OrderLine::singleton())
+        .add(traits::OrderProcessor::transient())
+        .build_provider()
+        .unwrap();
+    let processor = provider.get_required::<traits::OrderProcessor>();
+
+    // act
+    let workflow = processor.workflows.create(42);
+    let line = processor.lines.create("ABC-123".into(), 2);
+
+    // assert
+    assert_eq!(workflow.order_id(), 42);
+    assert_eq!(workflow.echo(), "Success!");
+    assert_eq!(&line.sku, "ABC-123");
+    assert_eq!(line.quantity, 2);
+    assert_eq!(line.bar.echo(), "Success!");
+}
+
+#[test]
+fn inject_should_not_confuse_runtime_argument_with_service_provider() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::singleton())
+        .add(traits::Shipment::transient())
+        .build_provider()
+        .unwrap();
+    let factory = provider.get_required::<factory::Factory<Ref<traits::Shipment>, (String,)>>();
+
+    // act
+    let shipment = factory.create("Contoso".into());
+
+    // assert
+    assert_eq!(&shipment.carrier, "Contoso");
+    assert_eq!(shipment.bar.echo(), "Success!");
+}
+
 #[test]
 fn inject_should_implemented_keyed_dependencies() {
     // arrange
@@ -538,3 +621,318 @@ fn inject_should_implement_trait_with_unqualified_conditional_attribute() {
     // assert
     assert_eq!("Success!", foo.echo());
 }
+
+#[test]
+fn inject_should_dispose_disposable_struct_when_scope_is_dropped() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Connection::scoped().disposable::<structs::Connection>())
+        .build_provider()
+        .unwrap();
+    let connection;
+
+    // act
+    {
+        let scope = provider.create_scope();
+        connection = scope.get_required::<structs::Connection>();
+    }
+
+    // assert
+    assert!(connection.is_disposed());
+}
+
+#[test]
+fn inject_should_activate_struct_with_fallible_constructor() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(existing_as_self(structs::Settings {
+            port: String::from("8080"),
+        }))
+        .add(structs::Endpoint::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let endpoint = provider.try_get_required::<structs::Endpoint>().unwrap();
+
+    // assert
+    assert_eq!(endpoint.port, 8080);
+}
+
+#[test]
+fn inject_should_return_error_when_fallible_constructor_fails() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(existing_as_self(structs::Settings {
+            port: String::from("http"),
+        }))
+        .add(structs::Endpoint::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let result = provider.try_get_required::<structs::Endpoint>();
+
+    // assert
+    match result {
+        Err(ResolveError::Factory { service, error, .. }) => {
+            assert_eq!(service, Type::of::<structs::Endpoint>());
+            assert_eq!(&error.to_string(), "invalid digit found in string");
+        }
+        _ => panic!("expected a factory error"),
+    }
+}
+
+#[test]
+fn inject_should_return_error_with_path_when_fallible_constructor_of_dependency_fails() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(existing_as_self(structs::Settings {
+            port: String::from("http"),
+        }))
+        .add(structs::Endpoint::transient())
+        .add(structs::Server::transient())
+        .build_provider()
+        .unwrap();
+
+    // act
+    let error = provider.try_get_required::<structs::Server>().err().unwrap();
+
+    // assert
+    assert!(matches!(error, ResolveError::Factory { .. }));
+    assert_eq!(error.service(), &Type::of::<structs::Endpoint>());
+    assert_eq!(
+        error.path(),
+        &[Type::of::<structs::Server>(), Type::of::<structs::Endpoint>()]
+    );
+}
+
+#[test]
+fn inject_should_resolve_struct_keyed_by_value() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Connection::singleton().with_value_key("primary"))
+        .add(structs::Connection::singleton().with_value_key("replica"))
+        .build_provider()
+        .unwrap();
+
+    // act
+    let primary = provider.get_required_by_value_key::<structs::Connection>("primary");
+    let replica = provider.get_required_by_value_key::<structs::Connection>("replica");
+
+    // assert
+    assert!(!Ref::ptr_eq(&primary, &replica));
+    assert!(provider.get::<structs::Connection>().is_none());
+}
+
+#[test]
+fn injectable_should_declare_every_resolved_dependency() {
+    // arrange
+    let provider = ServiceCollection::new()
+        .add(structs::Bar::transient())
+        .add(structs::Foo::transient())
+        .add(structs::UnitStruct::singleton())
+        .add(structs::NormalStruct::transient())
+        .build_strict_provider()
+        .unwrap();
+
+    // act
+    let _ = provider.get_required::<structs::Foo>();
+    let normal = provider.get_required::<structs::NormalStruct>();
+    let _ = normal.lazy.value();
+
+    // assert
+    provider.assert_dependencies_declared();
+}
+
+#[test]
+fn validate_should_fail_when_lazy_dependency_of_singleton_is_scoped() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(traits::BarImpl::scoped())
+        .add(traits::OneLazyFoo::singleton());
+
+    // act
+    let result = validate(&services);
+
+    // assert
+    assert_eq!(result.err().unwrap().results()[0].rule(), "SingletonDependsOnScoped");
+}
+
+#[test]
+fn validate_should_fail_when_factory_dependency_is_missing() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(traits::FactoryFoo::singleton());
+
+    // act
+    let result = validate(&services);
+
+    // assert
+    assert_eq!(result.err().unwrap().results()[0].rule(), "MissingRequiredType");
+}
+
+#[test]
+fn validate_should_not_fail_when_singleton_owns_scoped_dependency() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(traits::BarImpl::scoped())
+        .add(traits::OwnedFoo::singleton());
+
+    // act
+    let result = validate(&services);
+
+    // assert
+    assert!(result.is_ok());
+}
+
+#[test]
+fn validate_should_fail_when_assisted_factory_dependency_is_missing() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services.add(traits::OrderWorkflow::transient());
+
+    // act
+    let result = validate(&services);
+
+    // assert
+    assert_eq!(result.err().unwrap().results()[0].rule(), "MissingRequiredType");
+}
+
+#[test]
+fn validate_should_warn_when_singleton_injects_service_provider() {
+    // arrange
+    let mut services = ServiceCollection::new();
+
+    services
+        .add(traits::BarImpl::transient())
+        .add(traits::FooImpl::transient())
+        .add(containers::Container::singleton());
+
+    // act
+    let warnings = validate_with(&services, &ValidationOptions::default()).unwrap();
+
+    // assert
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].rule(), "SingletonDependsOnServiceProvider");
+    assert_eq!(warnings[0].service_type(), &Type::of::<containers::Container>());
+}
+
+#[test]
+#[should_panic(
+    expected = "The scoped service 'dyn more_di_tests::traits::Bar' cannot be resolved for the singleton service \
+                           'dyn more_di_tests::traits::Foo', which would capture it."
+)]
+fn validate_scopes_should_fail_when_singleton_resolves_lazy_scoped_service() {
+    // arrange
+    let options = ValidationOptions::default()
+        .remove_rule("SingletonDependsOnScoped")
+        .validate_scopes(true);
+    let provider = ServiceCollection::new()
+        .add(traits::BarImpl::scoped())
+        .add(traits::OneLazyFoo::singleton())
+        .build_provider_with(options)
+        .unwrap();
+    let scope = provider.create_scope();
+    let foo = scope.get_required::<dyn traits::Foo>();
+
+    // act
+    let _ = foo.echo();
+
+    // assert
+    // panics
+}
+
+#[test]
+fn start_all_should_start_injectable_hosted_services_in_dependency_order() {
+    // arrange
+    type Log = std::sync::Mutex<Vec<&'static str>>;
+    type HostResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+    #[injectable]
+    struct Database {
+        log: Ref<Log>,
+    }
+
+    impl HostedService for Database {
+        fn start(&self) -> HostResult {
+            self.log.lock().unwrap().push("database");
+            Ok(())
+        }
+
+        fn stop(&self) -> HostResult {
+            Ok(())
+        }
+    }
+
+    #[injectable(HostedService)]
+    struct Cache {
+        _database: Ref<Database>,
+        log: Ref<Log>,
+    }
+
+    impl HostedService for Cache {
+        fn start(&self) -> HostResult {
+            self.log.lock().unwrap().push("cache");
+            Ok(())
+        }
+
+        fn stop(&self) -> HostResult {
+            Ok(())
+        }
+    }
+
+    let provider = ServiceCollection::new()
+        .add(Cache::singleton())
+        .add(Database::singleton())
+        .add(
+            singleton::<dyn HostedService, Database>()
+                .depends_on(exactly_one::<Database>())
+                .from(|sp| sp.get_required::<Database>()),
+        )
+        .add(existing::<Log, Log>(Box::new(Log::default())))
+        .build_provider()
+        .unwrap();
+    let log = provider.get_required::<Log>();
+
+    // act
+    provider.start_all().unwrap();
+
+    // assert
+    assert_eq!(&*log.lock().unwrap(), &["database", "cache"]);
+}
+
+#[test]
+fn inject_should_report_type_mismatch_when_disposable_type_is_not_service_type() {
+    // arrange
+    #[injectable(Bar)]
+    struct Pipe;
+
+    impl Bar for Pipe {
+        fn echo(&self) -> &str {
+            "Success!"
+        }
+    }
+
+    impl Disposable for Pipe {
+        fn dispose(&self) {}
+    }
+
+    let provider = ServiceCollection::new()
+        .add(Pipe::scoped().disposable::<Pipe>())
+        .build_provider()
+        .unwrap();
+    let scope = provider.create_scope();
+
+    // act
+    let result = scope.try_get_required::<dyn Bar>();
+
+    // assert
+    assert!(matches!(result, Err(ResolveError::TypeMismatch { .. })));
+}
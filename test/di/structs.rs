@@ -1,7 +1,9 @@
 #![allow(dead_code)]
 
-use di::{inject, injectable, lazy::Lazy, Ref};
+use di::{inject, injectable, lazy::Lazy, Disposable, Ref};
 use std::marker::PhantomData;
+use std::num::ParseIntError;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 // demonstrates using a user-defined alias for Ref<T>
 pub type ServiceRef<T> = Ref<T>;
@@ -130,3 +132,42 @@ impl QualifiedConditional {
         Self(bar)
     }
 }
+
+#[injectable]
+pub struct Connection {
+    disposed: AtomicBool,
+}
+
+impl Connection {
+    pub fn is_disposed(&self) -> bool {
+        self.disposed.load(Ordering::Relaxed)
+    }
+}
+
+impl Disposable for Connection {
+    fn dispose(&self) {
+        self.disposed.store(true, Ordering::Relaxed);
+    }
+}
+
+pub struct Settings {
+    pub port: String,
+}
+
+pub struct Endpoint {
+    pub port: u16,
+}
+
+#[injectable]
+impl Endpoint {
+    pub fn new(settings: Ref<Settings>) -> Result<Self, ParseIntError> {
+        Ok(Self {
+            port: settings.port.parse()?,
+        })
+    }
+}
+
+#[injectable]
+pub struct Server {
+    pub endpoint: Ref<Endpoint>,
+}
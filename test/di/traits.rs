@@ -1,5 +1,5 @@
-use di::{inject, injectable, lazy::Lazy, KeyedRef, Ref};
-use std::fmt::Debug;
+use di::{factory::Factory, inject, injectable, lazy::Lazy, owned::Owned, KeyedRef, Ref};
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
 
 #[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
 pub trait Foo {
@@ -109,6 +109,77 @@ impl OneLazyFoo {
     }
 }
 
+#[injectable]
+pub struct FactoryFoo {
+    pub bar: Factory<Ref<dyn Bar>>,
+}
+
+#[injectable]
+pub struct OwnedFoo {
+    pub bar: Owned<Ref<dyn Bar>>,
+}
+
+#[cfg_attr(feature = "async", maybe_impl::traits(Send, Sync))]
+pub trait Workflow {
+    fn order_id(&self) -> u64;
+    fn echo(&self) -> &str;
+}
+
+pub struct OrderWorkflow {
+    bar: Ref<dyn Bar>,
+    order_id: u64,
+}
+
+impl Workflow for OrderWorkflow {
+    fn order_id(&self) -> u64 {
+        self.order_id
+    }
+
+    fn echo(&self) -> &str {
+        self.bar.echo()
+    }
+}
+
+// 'order_id' is supplied by the caller of the generated factory
+#[injectable(Workflow)]
+impl OrderWorkflow {
+    fn new(bar: Ref<dyn Bar>, #[arg] order_id: u64) -> Self {
+        Self { bar, order_id }
+    }
+}
+
+pub struct OrderLine {
+    pub bar: Ref<dyn Bar>,
+    pub sku: String,
+    pub quantity: u32,
+}
+
+#[injectable]
+impl OrderLine {
+    fn new(#[arg] sku: String, bar: Ref<dyn Bar>, #[arg] quantity: u32) -> Self {
+        Self { bar, sku, quantity }
+    }
+}
+
+pub struct Shipment {
+    pub bar: Ref<dyn Bar>,
+    pub carrier: String,
+}
+
+// 'sp' is the same name the generated factory uses for its service provider
+#[injectable]
+impl Shipment {
+    fn new(bar: Ref<dyn Bar>, #[arg] sp: String) -> Self {
+        Self { bar, carrier: sp }
+    }
+}
+
+#[injectable]
+pub struct OrderProcessor {
+    pub workflows: Factory<Ref<dyn Workflow>, (u64,)>,
+    pub lines: Ref<Factory<Ref<OrderLine>, (String, u32)>>,
+}
+
 pub struct MaybeLazyFoo {
     bar: Lazy<Option<Ref<dyn Bar>>>,
 }
@@ -182,17 +253,17 @@ impl Thing2 {
 
 impl Thing for Thing1 {}
 
-impl ToString for Thing1 {
-    fn to_string(&self) -> String {
-        String::from(std::any::type_name::<Self>())
+impl Display for Thing1 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(std::any::type_name::<Self>())
     }
 }
 
 impl Thing for Thing2 {}
 
-impl ToString for Thing2 {
-    fn to_string(&self) -> String {
-        String::from(std::any::type_name::<Self>())
+impl Display for Thing2 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        f.write_str(std::any::type_name::<Self>())
     }
 }
 